@@ -0,0 +1,160 @@
+use std::fmt::Display;
+
+/// Allocations made by one part, only available with the `alloc-stats` feature
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct AllocStats {
+    pub count: u64,
+    /// total over all allocations, memory that is freed again still counts
+    pub bytes: u64,
+    /// most memory allocated at the same time
+    pub peak: u64,
+}
+
+/// Binary units with one decimal, like `3.4 KiB`
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+impl Display for AllocStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} allocs, {}, peak {}",
+            self.count,
+            format_bytes(self.bytes),
+            format_bytes(self.peak)
+        )
+    }
+}
+
+#[cfg(feature = "alloc-stats")]
+mod counting {
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        cell::Cell,
+    };
+
+    use super::AllocStats;
+
+    // per thread, so parts running in parallel don't count each other's allocations.
+    // const initialized and without destructors, so accessing them never allocates
+    thread_local! {
+        static COUNT: Cell<u64> = const { Cell::new(0) };
+        static BYTES: Cell<u64> = const { Cell::new(0) };
+        /// signed, memory allocated on another thread may be freed here
+        static LIVE: Cell<i64> = const { Cell::new(0) };
+        static PEAK: Cell<i64> = const { Cell::new(0) };
+    }
+
+    fn record_alloc(size: usize) {
+        // try_with fails while the thread is shutting down, those allocations don't matter
+        let _ = COUNT.try_with(|count| count.set(count.get() + 1));
+        let _ = BYTES.try_with(|bytes| bytes.set(bytes.get() + size as u64));
+        record_live(size as i64);
+    }
+
+    fn record_live(change: i64) {
+        let _ = LIVE.try_with(|live| {
+            live.set(live.get() + change);
+            let _ = PEAK.try_with(|peak| peak.set(peak.get().max(live.get())));
+        });
+    }
+
+    struct CountingAllocator;
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            record_alloc(layout.size());
+            System.alloc(layout)
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            record_alloc(layout.size());
+            System.alloc_zeroed(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            record_live(-(layout.size() as i64));
+            System.dealloc(ptr, layout)
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            record_live(-(layout.size() as i64));
+            record_alloc(new_size);
+            System.realloc(ptr, layout, new_size)
+        }
+    }
+
+    #[global_allocator]
+    static GLOBAL: CountingAllocator = CountingAllocator;
+
+    pub fn measure<R>(f: impl FnOnce() -> R) -> (R, AllocStats) {
+        COUNT.set(0);
+        BYTES.set(0);
+        LIVE.set(0);
+        PEAK.set(0);
+        let result = f();
+        let stats = AllocStats {
+            count: COUNT.get(),
+            bytes: BYTES.get(),
+            peak: PEAK.get().max(0) as u64,
+        };
+        (result, stats)
+    }
+}
+
+/// Runs f and counts its allocations on the current thread. None without the `alloc-stats` feature
+pub fn measure<R>(f: impl FnOnce() -> R) -> (R, Option<AllocStats>) {
+    #[cfg(feature = "alloc-stats")]
+    {
+        let (result, stats) = counting::measure(f);
+        (result, Some(stats))
+    }
+    #[cfg(not(feature = "alloc-stats"))]
+    (f(), None)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let stats = AllocStats {
+            count: 3,
+            bytes: 3 * 1024 + 512,
+            peak: 100,
+        };
+        assert_eq!(stats.to_string(), "3 allocs, 3.5 KiB, peak 100 B");
+        assert_eq!(format_bytes(5 * 1024 * 1024), "5.0 MiB");
+    }
+
+    #[test]
+    fn test_measure() {
+        let (len, stats) = measure(|| {
+            let first = vec![0u8; 1000];
+            drop(first);
+            vec![0u8; 600].len()
+        });
+        assert_eq!(len, 600);
+        if cfg!(feature = "alloc-stats") {
+            let stats = stats.unwrap();
+            assert!(stats.count >= 2);
+            assert!(stats.bytes >= 1600);
+            // the first vector was freed before the second one was allocated
+            assert!((1000..1600).contains(&stats.peak));
+        } else {
+            assert_eq!(stats, None);
+        }
+    }
+}
@@ -0,0 +1,196 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    io,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    input,
+    runner::{PartReport, PartStatus},
+};
+
+pub const ANSWERS_FILE: &str = "answers.txt";
+
+/// The answers file lives next to the input directory
+pub fn answers_path(input_dir: &Path) -> PathBuf {
+    input_dir
+        .parent()
+        .unwrap_or(Path::new("."))
+        .join(ANSWERS_FILE)
+}
+
+/// (day, part, variant)
+type Key = (usize, usize, Option<String>);
+
+/// Known answers, one per line in the form `07_p3: answer`, or `07_p3.alice: answer` for variants.
+/// Empty lines and lines starting with # are ignored
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct KnownAnswers {
+    answers: BTreeMap<Key, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Unknown,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "PASS"),
+            Verdict::Fail { expected } => write!(f, "FAIL (expected {})", expected),
+            Verdict::Unknown => write!(f, "UNKNOWN"),
+        }
+    }
+}
+
+fn parse_key(key: &str) -> Option<Key> {
+    let (key, variant) = match key.split_once('.') {
+        Some((key, variant)) => (key, Some(variant.to_string())),
+        None => (key, None),
+    };
+    let (day, part) = key.split_once("_p")?;
+    Some((day.parse().ok()?, part.parse().ok()?, variant))
+}
+
+fn report_key(report: &PartReport) -> Key {
+    (report.day, report.part, report.variant.clone())
+}
+
+impl KnownAnswers {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let mut answers = BTreeMap::new();
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, answer) = line
+                .split_once(':')
+                .and_then(|(key, answer)| Some((parse_key(key.trim())?, answer.trim())))
+                .map(|(key, answer)| (key, answer.to_string()))
+                .ok_or_else(|| {
+                    format!("line {}: expected `DD_pP: answer`, got `{}`", i + 1, line)
+                })?;
+            answers.insert(key, answer);
+        }
+        Ok(Self { answers })
+    }
+
+    /// A missing file is treated as "no known answers"
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(s) => Self::parse(&s).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        std::fs::write(path, self.to_string())
+    }
+
+    pub fn get(&self, day: usize, part: usize, variant: Option<&str>) -> Option<&str> {
+        self.answers
+            .get(&(day, part, variant.map(str::to_string)))
+            .map(String::as_str)
+    }
+
+    /// Remembers the answers of all successful reports, replacing older ones
+    pub fn record(&mut self, reports: &[PartReport]) {
+        for report in reports {
+            if let PartStatus::Answer(answer) = &report.status {
+                self.answers.insert(report_key(report), answer.to_string());
+            }
+        }
+    }
+
+    pub fn verify(&self, report: &PartReport) -> Verdict {
+        let Some(expected) = self.get(report.day, report.part, report.variant.as_deref()) else {
+            return Verdict::Unknown;
+        };
+        match &report.status {
+            PartStatus::Answer(answer) if answer.to_string() == expected => Verdict::Pass,
+            _ => Verdict::Fail {
+                expected: expected.to_string(),
+            },
+        }
+    }
+}
+
+impl Display for KnownAnswers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for ((day, part, variant), answer) in &self.answers {
+            let key = input::input_key(*day, *part, variant.as_deref());
+            writeln!(f, "{}: {}", key, answer)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{path::PathBuf, time::Duration};
+
+    use super::*;
+    use crate::input::InputError;
+
+    fn report(day: usize, part: usize, status: PartStatus) -> PartReport {
+        PartReport {
+            day,
+            part,
+            variant: None,
+            status,
+            elapsed: Duration::ZERO,
+            parse_time: None,
+            allocs: None,
+            input: PathBuf::new(),
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let answers =
+            KnownAnswers::parse("# comment\n04_p3: 8\n\n10_p1:  PTBV \n04_p3.alice: 9").unwrap();
+        assert_eq!(answers.get(4, 3, None), Some("8"));
+        assert_eq!(answers.get(4, 3, Some("alice")), Some("9"));
+        assert_eq!(answers.get(10, 1, None), Some("PTBV"));
+        assert_eq!(answers.get(4, 1, None), None);
+        assert_eq!(
+            answers.to_string(),
+            "04_p3: 8\n04_p3.alice: 9\n10_p1: PTBV\n"
+        );
+        assert!(KnownAnswers::parse("4/3: 8").is_err());
+    }
+
+    #[test]
+    fn test_verify() {
+        let mut answers = KnownAnswers::parse("04_p3: 8").unwrap();
+        let answer = |a: i32| PartStatus::Answer(a.into());
+        assert_eq!(answers.verify(&report(4, 3, answer(8))), Verdict::Pass);
+        assert_eq!(
+            answers.verify(&report(4, 3, PartStatus::NoSolution)),
+            Verdict::Fail {
+                expected: "8".to_string()
+            }
+        );
+        assert_eq!(answers.verify(&report(4, 1, answer(8))), Verdict::Unknown);
+
+        let no_input = PartStatus::NoInput(InputError::Missing(PathBuf::from("04_p1.txt")));
+        let variant = PartReport {
+            variant: Some("bob".to_string()),
+            ..report(4, 3, answer(7))
+        };
+        assert_eq!(answers.verify(&variant), Verdict::Unknown);
+        answers.record(&[
+            report(4, 3, answer(9)),
+            report(4, 1, no_input),
+            variant.clone(),
+        ]);
+        assert_eq!(answers.to_string(), "04_p3: 9\n04_p3.bob: 7\n");
+        assert_eq!(answers.verify(&variant), Verdict::Pass);
+    }
+}
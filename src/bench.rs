@@ -0,0 +1,255 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    io,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    history::{self, Comparison},
+    input,
+    runner::{self, PartStatus, PlannedRun, RunConfig},
+    solution::DynSolution,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BenchOptions {
+    pub iterations: usize,
+    /// runs before measuring, so caches and the allocator are warmed up
+    pub warmup: usize,
+    /// store the results under this name in target/bench
+    pub save_baseline: Option<String>,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self {
+            iterations: 20,
+            warmup: 3,
+            save_baseline: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BenchStats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl BenchStats {
+    /// samples must not be empty
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let n = sorted.len();
+        let median = if n.is_multiple_of(2) {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        } else {
+            sorted[n / 2]
+        };
+        let mean = sorted.iter().map(Duration::as_secs_f64).sum::<f64>() / n as f64;
+        let variance = sorted
+            .iter()
+            .map(|s| (s.as_secs_f64() - mean).powi(2))
+            .sum::<f64>()
+            / n as f64;
+        Self {
+            min: sorted[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+impl Display for BenchStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "min {:?}  median {:?}  mean {:?}  stddev {:?}",
+            self.min, self.median, self.mean, self.stddev
+        )
+    }
+}
+
+/// Saved medians per (day, part), one `DD_pP nanoseconds` line each
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Baseline {
+    medians: BTreeMap<(usize, usize), Duration>,
+}
+
+impl Baseline {
+    pub fn path(name: &str) -> PathBuf {
+        history::target_dir()
+            .join("bench")
+            .join(format!("{}.txt", name))
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        let mut medians = BTreeMap::new();
+        for line in s.lines().filter(|l| !l.trim().is_empty()) {
+            let (key, nanos) = line.split_once(' ')?;
+            let (day, part) = key.split_once("_p")?;
+            let nanos: u64 = nanos.trim().parse().ok()?;
+            medians.insert(
+                (day.parse().ok()?, part.parse().ok()?),
+                Duration::from_nanos(nanos),
+            );
+        }
+        Some(Self { medians })
+    }
+
+    pub fn load(name: &str) -> io::Result<Self> {
+        let s = std::fs::read_to_string(Self::path(name))?;
+        Self::parse(&s)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "malformed baseline"))
+    }
+
+    /// Merges into an existing baseline of the same name
+    pub fn save(&self, name: &str) -> io::Result<()> {
+        let path = Self::path(name);
+        let mut merged = Self::load(name).unwrap_or_default();
+        merged.extend(self);
+        std::fs::create_dir_all(path.parent().unwrap())?;
+        std::fs::write(path, merged.to_string())
+    }
+
+    pub fn get(&self, day: usize, part: usize) -> Option<Duration> {
+        self.medians.get(&(day, part)).copied()
+    }
+
+    pub fn insert(&mut self, day: usize, part: usize, median: Duration) {
+        self.medians.insert((day, part), median);
+    }
+
+    /// Parts in both keep the timing from other
+    pub fn extend(&mut self, other: &Baseline) {
+        self.medians.extend(&other.medians);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.medians.is_empty()
+    }
+
+    /// (day, part, median) in day/part order
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize, Duration)> + '_ {
+        self.medians
+            .iter()
+            .map(|(&(day, part), &median)| (day, part, median))
+    }
+}
+
+impl Display for Baseline {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for ((day, part), median) in &self.medians {
+            writeln!(f, "{:0>2}_p{} {}", day, part, median.as_nanos())?;
+        }
+        Ok(())
+    }
+}
+
+/// relative change from old to new in percent
+pub fn change_percent(old: Duration, new: Duration) -> f64 {
+    (new.as_secs_f64() - old.as_secs_f64()) / old.as_secs_f64() * 100.0
+}
+
+/// Benchmarks the given parts of one day, printing the statistics for each part
+pub fn bench_day(
+    solution: &'static dyn DynSolution,
+    parts: &[usize],
+    config: &RunConfig,
+    options: &BenchOptions,
+    compare: Option<&Comparison>,
+) -> io::Result<()> {
+    let day = solution.day();
+    let mut baseline = Baseline::default();
+    for &part in parts {
+        // the first run doubles as a check that the part works at all
+        let report = runner::run_part(&PlannedRun::new(solution, part), config);
+        if !matches!(report.status, PartStatus::Answer(_)) {
+            println!("{}", report);
+            continue;
+        }
+        let input = input::read_input(&report.input).expect("input was readable a moment ago");
+        for _ in 1..options.warmup {
+            let _ = solution.run_part(part, &input);
+        }
+        let samples = (0..options.iterations.max(1))
+            .map(|_| {
+                let now = Instant::now();
+                let _ = solution.run_part(part, &input);
+                now.elapsed()
+            })
+            .collect::<Vec<_>>();
+        let stats = BenchStats::from_samples(&samples);
+        println!(
+            "Day {} Part {}: {} runs after {} warm-up",
+            day,
+            part,
+            samples.len(),
+            options.warmup.max(1)
+        );
+        println!("  {}", stats);
+        if let Some(compare) = compare {
+            match compare.baseline.get(day, part) {
+                Some(old) => println!(
+                    "  {}: median {:?} -> {:+.1}%{}",
+                    compare.name,
+                    old,
+                    change_percent(old, stats.median),
+                    if compare.is_regression(old, stats.median) {
+                        " REGRESSION"
+                    } else {
+                        ""
+                    }
+                ),
+                None => println!("  {}: no data", compare.name),
+            }
+        }
+        baseline.insert(day, part, stats.median);
+    }
+    if let Some(name) = &options.save_baseline {
+        baseline.save(name)?;
+        println!(
+            "Saved baseline {} to {}",
+            name,
+            Baseline::path(name).display()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let samples = [4, 1, 3, 2].map(Duration::from_millis);
+        let stats = BenchStats::from_samples(&samples);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_micros(2500));
+        assert_eq!(stats.mean.as_micros(), 2500);
+        // population standard deviation of 1, 2, 3, 4 is sqrt(1.25)
+        assert_eq!(stats.stddev.as_micros(), 1118);
+    }
+
+    #[test]
+    fn test_baseline_roundtrip() {
+        let mut baseline = Baseline::default();
+        baseline.insert(7, 3, Duration::from_nanos(1234));
+        baseline.insert(10, 1, Duration::from_nanos(5));
+        let s = baseline.to_string();
+        assert_eq!(s, "07_p3 1234\n10_p1 5\n");
+        assert_eq!(Baseline::parse(&s), Some(baseline));
+        assert_eq!(Baseline::parse("07_p3 soon"), None);
+        assert_eq!(
+            change_percent(Duration::from_secs(2), Duration::from_secs(3)),
+            50.0
+        );
+    }
+}
@@ -0,0 +1,299 @@
+use std::{fmt::Display, path::PathBuf, time::Duration};
+
+use crate::{
+    bench::BenchOptions, log::Level, runner::format::OutputFormat, scaffold::ANSWER_TYPES,
+};
+
+pub const USAGE: &str = "usage: everybody-codes [bench|new] [<day>|all] [options]
+
+commands:
+  bench                 time the selected day repeatedly instead of running it once
+  new                   create src/dayN.rs from the template and register it
+
+options:
+  -p, --part <1|2|3>    only run this part
+  --input <path>        read the input from this file, or stdin for -, needs a day and a part
+  --input-dir <dir>     directory containing DD_pP.txt, defaults to $EC_INPUT_DIR or input/ in the crate root
+  -j, --jobs <n>        run parts on n threads, 0 uses all cores (default 1)
+  --timeout <time>      give up on parts running longer than this, e.g. 30s or 500ms
+  --format <format>     text, json (one object per line) or csv (default text)
+  -v, -vv               log debug or trace output of the solutions
+  -q, --quiet           no log output at all
+  --log-file <path>     write the log to this file instead of stderr
+  --watch               re-run whenever one of the input files changes, until interrupted
+  --compare <name>      flag parts that got slower than in baseline <name>, or the previous run for `previous`
+  --threshold <percent> how much slower a part may get before it is flagged (default 10)
+  --verify              compare the answers against answers.txt, fails on any mismatch
+  --record              store the current answers in answers.txt
+
+bench options:
+  --iterations <n>      measured runs per part (default 20)
+  --warmup <n>          unmeasured runs before measuring (default 3)
+  --save <name>         store the medians as baseline <name> in target/bench
+
+new options:
+  --answer-type <type>  one of i32, i64, usize, u128, String (default i64)";
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    #[default]
+    Run,
+    Bench,
+    New,
+}
+
+/// Which days should be run
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum DaySelection {
+    /// No day given, run the most recent one
+    #[default]
+    Latest,
+    All,
+    Day(usize),
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Args {
+    pub mode: Mode,
+    pub days: DaySelection,
+    pub part: Option<usize>,
+    pub input_dir: Option<PathBuf>,
+    /// replaces the input file of a single part, `-` is stdin
+    pub input: Option<PathBuf>,
+    /// None runs sequentially, Some(0) picks the number of cores
+    pub jobs: Option<usize>,
+    pub format: OutputFormat,
+    pub timeout: Option<Duration>,
+    pub log_level: Level,
+    pub log_file: Option<PathBuf>,
+    pub watch: bool,
+    pub verify: bool,
+    pub record: bool,
+    pub bench: BenchOptions,
+    /// baseline name or [`crate::history::PREVIOUS`]
+    pub compare: Option<String>,
+    /// None means [`crate::history::DEFAULT_THRESHOLD`]
+    pub threshold: Option<usize>,
+    /// only used by new, None means the template default
+    pub answer_type: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CliError {
+    /// --help was passed, not really an error but we want to print the usage and stop
+    Help,
+    Invalid(String),
+}
+
+impl Display for CliError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CliError::Help => write!(f, "{}", USAGE),
+            CliError::Invalid(msg) => write!(f, "{}\n{}", msg, USAGE),
+        }
+    }
+}
+
+fn require_value(flag: &str, value: Option<String>) -> Result<String, CliError> {
+    value.ok_or_else(|| CliError::Invalid(format!("{} needs a value", flag)))
+}
+
+fn parse_number(flag: &str, value: Option<String>) -> Result<usize, CliError> {
+    let value = require_value(flag, value)?;
+    value
+        .parse()
+        .map_err(|_| CliError::Invalid(format!("{} expects a number, got {}", flag, value)))
+}
+
+/// Plain numbers are seconds, otherwise a `ms` or `s` suffix is required
+fn parse_duration(flag: &str, value: Option<String>) -> Result<Duration, CliError> {
+    let value = require_value(flag, value)?;
+    let (number, to_duration): (_, fn(u64) -> Duration) = match value.strip_suffix("ms") {
+        Some(millis) => (millis, Duration::from_millis),
+        None => (
+            value.strip_suffix('s').unwrap_or(&value),
+            Duration::from_secs,
+        ),
+    };
+    match number.parse() {
+        Ok(n) if n > 0 => Ok(to_duration(n)),
+        _ => Err(CliError::Invalid(format!(
+            "{} expects a duration like 30s or 500ms, got {}",
+            flag, value
+        ))),
+    }
+}
+
+fn parse_part(value: Option<String>) -> Result<usize, CliError> {
+    let value = require_value("--part", value)?;
+    match value.parse() {
+        Ok(part @ 1..=3) => Ok(part),
+        _ => Err(CliError::Invalid(format!("invalid part: {}", value))),
+    }
+}
+
+impl Args {
+    /// Parses the arguments, without the binary name
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, CliError> {
+        let mut parsed = Self::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-h" | "--help" => return Err(CliError::Help),
+                "-p" | "--part" => parsed.part = Some(parse_part(args.next())?),
+                "--input" => parsed.input = Some(require_value(&arg, args.next())?.into()),
+                "--input-dir" => parsed.input_dir = Some(require_value(&arg, args.next())?.into()),
+                "-j" | "--jobs" => parsed.jobs = Some(parse_number(&arg, args.next())?),
+                "--format" => {
+                    parsed.format = require_value(&arg, args.next())?
+                        .parse()
+                        .map_err(CliError::Invalid)?
+                }
+                "--timeout" => parsed.timeout = Some(parse_duration(&arg, args.next())?),
+                "-v" => parsed.log_level = parsed.log_level.more(),
+                "-vv" => parsed.log_level = parsed.log_level.more().more(),
+                "-q" | "--quiet" => parsed.log_level = Level::Off,
+                "--log-file" => parsed.log_file = Some(require_value(&arg, args.next())?.into()),
+                "--watch" => parsed.watch = true,
+                "--verify" => parsed.verify = true,
+                "--record" => parsed.record = true,
+                "--iterations" => parsed.bench.iterations = parse_number(&arg, args.next())?,
+                "--warmup" => parsed.bench.warmup = parse_number(&arg, args.next())?,
+                "--save" => parsed.bench.save_baseline = Some(require_value(&arg, args.next())?),
+                "--compare" => parsed.compare = Some(require_value(&arg, args.next())?),
+                "--threshold" => parsed.threshold = Some(parse_number(&arg, args.next())?),
+                "--answer-type" => {
+                    let answer_type = require_value(&arg, args.next())?;
+                    if !ANSWER_TYPES.contains(&answer_type.as_str()) {
+                        return Err(CliError::Invalid(format!(
+                            "unsupported answer type: {}",
+                            answer_type
+                        )));
+                    }
+                    parsed.answer_type = Some(answer_type);
+                }
+                "bench" => parsed.mode = Mode::Bench,
+                "new" => parsed.mode = Mode::New,
+                "all" => parsed.days = DaySelection::All,
+                _ => match arg.parse() {
+                    Ok(day) if day > 0 => parsed.days = DaySelection::Day(day),
+                    _ => return Err(CliError::Invalid(format!("unknown argument: {}", arg))),
+                },
+            }
+        }
+        Ok(parsed)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Args, CliError> {
+        Args::parse(args.iter().map(|s| s.to_string()))
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse(&[]), Ok(Args::default()));
+        assert_eq!(
+            parse(&["bench", "9", "--iterations", "5", "--compare", "main"]),
+            Ok(Args {
+                mode: Mode::Bench,
+                days: DaySelection::Day(9),
+                bench: BenchOptions {
+                    iterations: 5,
+                    ..BenchOptions::default()
+                },
+                compare: Some("main".to_string()),
+                ..Args::default()
+            })
+        );
+        assert_eq!(
+            parse(&[
+                "7",
+                "--part",
+                "3",
+                "--verify",
+                "-j",
+                "4",
+                "--timeout",
+                "500ms",
+                "--watch"
+            ]),
+            Ok(Args {
+                days: DaySelection::Day(7),
+                part: Some(3),
+                jobs: Some(4),
+                timeout: Some(Duration::from_millis(500)),
+                watch: true,
+                verify: true,
+                ..Args::default()
+            })
+        );
+        assert_eq!(
+            parse(&["1", "-v", "--log-file", "ec.log", "-p", "2", "--input", "-"]),
+            Ok(Args {
+                days: DaySelection::Day(1),
+                part: Some(2),
+                input: Some(PathBuf::from("-")),
+                log_level: Level::Debug,
+                log_file: Some(PathBuf::from("ec.log")),
+                ..Args::default()
+            })
+        );
+        assert_eq!(parse(&["-vv"]).map(|args| args.log_level), Ok(Level::Trace));
+        assert_eq!(parse(&["-q"]).map(|args| args.log_level), Ok(Level::Off));
+        assert_eq!(
+            parse(&["new", "11", "--answer-type", "String"]),
+            Ok(Args {
+                mode: Mode::New,
+                days: DaySelection::Day(11),
+                answer_type: Some("String".to_string()),
+                ..Args::default()
+            })
+        );
+        assert_eq!(
+            parse(&[
+                "all",
+                "--record",
+                "--input-dir",
+                "/tmp/in",
+                "--format",
+                "json",
+                "--compare",
+                "previous",
+                "--threshold",
+                "25"
+            ]),
+            Ok(Args {
+                days: DaySelection::All,
+                compare: Some("previous".to_string()),
+                threshold: Some(25),
+                format: OutputFormat::Json,
+                input_dir: Some(PathBuf::from("/tmp/in")),
+                record: true,
+                ..Args::default()
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(parse(&["0"]).is_err());
+        assert!(parse(&["7", "--part", "4"]).is_err());
+        assert!(parse(&["7", "--part"]).is_err());
+        assert!(parse(&["seven"]).is_err());
+        assert!(parse(&["--input-dir"]).is_err());
+        assert!(parse(&["bench", "--warmup", "many"]).is_err());
+        assert!(parse(&["new", "11", "--answer-type", "f64"]).is_err());
+        assert!(parse(&["--format", "xml"]).is_err());
+        assert!(parse(&["--timeout", "0"]).is_err());
+        assert!(parse(&["--timeout", "1m"]).is_err());
+        assert_eq!(
+            parse(&["--timeout", "30"]).map(|args| args.timeout),
+            parse(&["--timeout", "30s"]).map(|args| args.timeout)
+        );
+        assert_eq!(parse(&["--help"]), Err(CliError::Help));
+    }
+}
@@ -1,66 +1,76 @@
-use core::panic;
-
 use itertools::Itertools;
 
-use crate::solution::Solution;
+use crate::solution::{ParseError, PartResult, Solution};
 
 pub struct Day1;
 
-fn p2_map(c: char) -> Option<i32> {
+fn invalid_creature(c: char, column: usize) -> ParseError {
+    ParseError::new(1, column, format!("invalid creature `{}`", c))
+}
+
+fn p1_map(c: char, column: usize) -> Result<i32, ParseError> {
+    match c {
+        'A' => Ok(0),
+        'B' => Ok(1),
+        'C' => Ok(3),
+        _ => Err(invalid_creature(c, column)),
+    }
+}
+
+fn p2_map(c: char, column: usize) -> Result<Option<i32>, ParseError> {
     match c {
-        'A' => Some(0),
-        'B' => Some(1),
-        'C' => Some(3),
-        'D' => Some(5),
-        'x' => None,
-        _ => panic!("Invalid input"),
+        'A' => Ok(Some(0)),
+        'B' => Ok(Some(1)),
+        'C' => Ok(Some(3)),
+        'D' => Ok(Some(5)),
+        'x' => Ok(None),
+        _ => Err(invalid_creature(c, column)),
     }
 }
 
+/// potions needed per creature, None for empty spots
+fn parse_creatures(input: &str) -> Result<Vec<Option<i32>>, ParseError> {
+    input
+        .trim_end()
+        .chars()
+        .enumerate()
+        .map(|(i, c)| p2_map(c, i + 1))
+        .collect()
+}
+
 impl Solution<i32> for Day1 {
     const DAY: usize = 1;
-    fn part1(&self, input: &str) -> Option<i32> {
-        Some(
-            input
-                .chars()
-                .map(|c| match c {
-                    'A' => 0,
-                    'B' => 1,
-                    'C' => 3,
-                    _ => panic!("Invalid input"),
-                })
-                .sum(),
-        )
+    fn part1(&self, input: &str) -> PartResult<i32> {
+        input
+            .trim_end()
+            .chars()
+            .enumerate()
+            .map(|(i, c)| p1_map(c, i + 1))
+            .sum::<Result<i32, _>>()
+            .map(Some)
     }
 
-    fn part2(&self, input: &str) -> Option<i32> {
-        Some(
-            input
-                .chars()
+    fn part2(&self, input: &str) -> PartResult<i32> {
+        Ok(Some(
+            parse_creatures(input)?
+                .into_iter()
                 .tuples()
-                .map(|(a, b)| {
-                    let a = p2_map(a);
-                    let b = p2_map(b);
-                    match (a, b) {
-                        (Some(a), Some(b)) => a + b + 2,
-                        (Some(a), None) => a,
-                        (None, Some(b)) => b,
-                        (None, None) => 0,
-                    }
+                .map(|(a, b)| match (a, b) {
+                    (Some(a), Some(b)) => a + b + 2,
+                    (Some(a), None) => a,
+                    (None, Some(b)) => b,
+                    (None, None) => 0,
                 })
                 .sum(),
-        )
+        ))
     }
 
-    fn part3(&self, input: &str) -> Option<i32> {
-        Some(
-            input
-                .chars()
+    fn part3(&self, input: &str) -> PartResult<i32> {
+        Ok(Some(
+            parse_creatures(input)?
+                .into_iter()
                 .tuples()
                 .map(|(a, b, c)| {
-                    let a = p2_map(a);
-                    let b = p2_map(b);
-                    let c = p2_map(c);
                     // one creature -> no bonus
                     // two creatures -> 1 bonus for each
                     // three creatures -> 2 bonus for all
@@ -76,6 +86,30 @@ impl Solution<i32> for Day1 {
                     }
                 })
                 .sum(),
-        )
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day1.part1("ABBAC\n"), Ok(Some(5)));
+        assert_eq!(
+            Day1.part1("ABXC"),
+            Err(ParseError::new(1, 3, "invalid creature `X`"))
+        );
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Day1.part2("AxBCDDCAxD"), Ok(Some(28)));
+    }
+
+    #[test]
+    fn test_part3() {
+        assert_eq!(Day1.part3("xBxAAABCDxCC"), Ok(Some(30)));
     }
 }
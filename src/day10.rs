@@ -1,17 +1,37 @@
-use itertools::Itertools;
-
 // Template for new days
-use crate::solution::Solution;
+use crate::{
+    log::{debug, trace},
+    solution::{ParseError, PartResult, Solution},
+};
 
 pub struct Day10;
 
-fn parse_input(input: &str, offset_x: usize, offset_y: usize) -> (Vec<Vec<char>>, Vec<Vec<char>>) {
+type Grid = (Vec<Vec<char>>, Vec<Vec<char>>);
+
+/// parses the 8x8 grid starting at the offset
+fn parse_input(input: &str, offset_x: usize, offset_y: usize) -> Result<Grid, ParseError> {
     let mut columns = vec![Vec::new(); 4];
     let mut rows = vec![Vec::new(); 4];
 
-    for (i, line) in input.lines().skip(offset_y).enumerate().take(8) {
+    let lines = input.lines().skip(offset_y).take(8).collect::<Vec<_>>();
+    if lines.len() < 8 {
+        return Err(ParseError::new(
+            offset_y + lines.len() + 1,
+            1,
+            "expected 8 lines per grid",
+        ));
+    }
+    for (i, line) in lines.into_iter().enumerate() {
+        let len = line.chars().count();
+        if len < offset_x + 8 {
+            return Err(ParseError::new(
+                offset_y + i + 1,
+                len + 1,
+                "expected 8 characters per grid line",
+            ));
+        }
         // first two and last two lines
-        if i < 2 || i >= 6 {
+        if !(2..6).contains(&i) {
             for (col, c) in line.chars().skip(2 + offset_x).take(4).enumerate() {
                 columns[col].push(c);
             }
@@ -20,15 +40,13 @@ fn parse_input(input: &str, offset_x: usize, offset_y: usize) -> (Vec<Vec<char>>
         // all others
         rows[i - 2].extend(line.chars().skip(offset_x).filter(|c| *c != '.'));
     }
-    (columns, rows)
+    Ok((columns, rows))
 }
 
 fn runic_word(columns: &[Vec<char>], rows: &[Vec<char>]) -> String {
     let mut result = String::with_capacity(10);
-    for y in 0..4 {
-        for x in 0..4 {
-            let row = &rows[y];
-            let col = &columns[x];
+    for row in rows.iter().take(4) {
+        for col in columns.iter().take(4) {
             // find only char present in both
             let mut found = false;
             for c in row {
@@ -51,7 +69,7 @@ fn runic_word_power(input: &str) -> usize {
         .chars()
         .enumerate()
         .map(|(i, c)| {
-            let base_power = c as u8 - 'A' as u8;
+            let base_power = c as u8 - b'A';
             let base_power = base_power as usize + 1;
             base_power * (i + 1)
         })
@@ -61,57 +79,57 @@ fn runic_word_power(input: &str) -> usize {
 impl Solution<String> for Day10 {
     const DAY: usize = 10;
 
-    fn part1(&self, input: &str) -> Option<String> {
-        let (columns, rows) = parse_input(input, 0, 0);
-        println!("{:?} {:?}", columns, rows);
+    fn part1(&self, input: &str) -> PartResult<String> {
+        let (columns, rows) = parse_input(input, 0, 0)?;
+        trace!("{:?} {:?}", columns, rows);
         let result = runic_word(&columns, &rows);
-        Some(result)
+        Ok(Some(result))
     }
 
-    fn part2(&self, input: &str) -> Option<String> {
+    fn part2(&self, input: &str) -> PartResult<String> {
         let mut words = Vec::new();
-        let width = input.lines().next().unwrap().chars().count();
+        let width = input.lines().next().unwrap_or_default().chars().count();
         let height = input.lines().count();
         for y in 0..((height + 1) / 9) {
             for x in 0..((width + 1) / 9) {
                 let offset_x = x * 9;
                 let offset_y = y * 9;
-                let (columns, rows) = parse_input(input, offset_x, offset_y);
-                println!("{:?} {:?}", columns, rows);
+                let (columns, rows) = parse_input(input, offset_x, offset_y)?;
+                trace!("{:?} {:?}", columns, rows);
                 let word = runic_word(&columns, &rows);
                 words.push(word);
             }
         }
-        println!("words: {:?}", words);
+        debug!("words: {:?}", words);
         let result = words
             .into_iter()
             .map(|w| runic_word_power(&w))
             .sum::<usize>()
             .to_string();
-        Some(result)
+        Ok(Some(result))
     }
 
-    fn part3(&self, input: &str) -> Option<String> {
+    fn part3(&self, input: &str) -> PartResult<String> {
         let mut words = Vec::new();
-        let width = input.lines().next().unwrap().chars().count();
+        let width = input.lines().next().unwrap_or_default().chars().count();
         let height = input.lines().count();
-        for y in 0..((height - 2) / 6) {
-            for x in 0..((width - 2) / 6) {
+        for y in 0..(height.saturating_sub(2) / 6) {
+            for x in 0..(width.saturating_sub(2) / 6) {
                 let offset_x = x * 6;
                 let offset_y = y * 6;
-                let (columns, rows) = parse_input(input, offset_x, offset_y);
-                println!("{:?} {:?}", columns, rows);
+                let (columns, rows) = parse_input(input, offset_x, offset_y)?;
+                trace!("{:?} {:?}", columns, rows);
                 let word = runic_word(&columns, &rows);
                 words.push(word);
             }
         }
-        println!("words: {:?}", words);
+        debug!("words: {:?}", words);
         let result = words
             .into_iter()
             .map(|w| runic_word_power(&w))
             .sum::<usize>()
             .to_string();
-        Some(result)
+        Ok(Some(result))
     }
 }
 
@@ -136,7 +154,13 @@ SG....MN
         let solution = get_solution();
         assert_eq!(
             solution.part1(test_input),
-            Some("PTBVRCZHFLJWGMNS".to_string())
+            Ok(Some("PTBVRCZHFLJWGMNS".to_string()))
+        );
+        assert_eq!(
+            solution.part1(
+                "**PCBS**\n**RLNW**\nBV....PT\nCR....HZ\nFL....JW\nSG....MN\n**FTZV**\n**GMJ"
+            ),
+            Err(ParseError::new(8, 6, "expected 8 characters per grid line"))
         );
     }
 
@@ -153,7 +177,7 @@ FL....JW
 SG....MN
 **FTZV**
 **GMJH**"#;
-        assert_eq!(solution.part2(test_input), Some("1851".to_string()));
+        assert_eq!(solution.part2(test_input), Ok(Some("1851".to_string())));
     }
 
     #[test]
@@ -173,6 +197,6 @@ FX....DJ....HV
 TJ....YK....LP
 **XRTK**BMSP**
 **DWZN**GCJV**"#;
-        assert_eq!(solution.part3(test_input), Some("3889".to_string()));
+        assert_eq!(solution.part3(test_input), Ok(Some("3889".to_string())));
     }
 }
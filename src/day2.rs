@@ -1,12 +1,20 @@
 #![allow(unreachable_code)]
-use crate::solution::Solution;
+use crate::solution::{ParseError, PartResult, Solution};
 use std::collections::VecDeque;
 
 pub struct Day2;
 
+/// the first line looks like `WORDS:THE,OWE,MES`
+fn parse_words(header: Option<&str>) -> Result<Vec<&str>, ParseError> {
+    header
+        .and_then(|header| header.split_once(':'))
+        .map(|(_, words)| words.split(',').collect())
+        .ok_or_else(|| ParseError::new(1, 1, "expected `WORDS:` followed by a list of words"))
+}
+
 fn get_match_mask(line: &str, words: &[&str], is_ring: bool) -> Vec<bool> {
     // vecdeques can be easily rotated
-    let mut mask = VecDeque::from_iter(std::iter::repeat(false).take(line.len()));
+    let mut mask = VecDeque::from_iter(std::iter::repeat_n(false, line.len()));
     let mut line = line.chars().collect::<VecDeque<_>>();
 
     if !is_ring {
@@ -44,38 +52,26 @@ fn get_match_mask(line: &str, words: &[&str], is_ring: bool) -> Vec<bool> {
 impl Solution<i32> for Day2 {
     const DAY: usize = 2;
 
-    fn part1(&self, input: &str) -> Option<i32> {
+    fn part1(&self, input: &str) -> PartResult<i32> {
         let mut lines = input.lines();
-        let words = lines
-            .next()
-            .unwrap()
-            .split(':')
-            .nth(1)
-            .unwrap()
-            .split(',')
-            .collect::<Vec<&str>>();
+        let words = parse_words(lines.next())?;
         // skip next line
         lines.next();
-        let sentence = lines.next().unwrap();
+        let sentence = lines
+            .next()
+            .ok_or_else(|| ParseError::new(3, 1, "expected an inscription"))?;
         let mut count = 0;
         for word in words {
             let this_word_count = sentence.matches(word).count();
 
             count += this_word_count;
         }
-        Some(count as i32)
+        Ok(Some(count as i32))
     }
 
-    fn part2(&self, input: &str) -> Option<i32> {
+    fn part2(&self, input: &str) -> PartResult<i32> {
         let mut lines = input.lines();
-        let words = lines
-            .next()
-            .unwrap()
-            .split(':')
-            .nth(1)
-            .unwrap()
-            .split(',')
-            .collect::<Vec<&str>>();
+        let words = parse_words(lines.next())?;
         // skip next line
         lines.next();
         let mut count = 0;
@@ -84,25 +80,29 @@ impl Solution<i32> for Day2 {
 
             count += rune_mask.iter().filter(|&&x| x).count();
         }
-        Some(count as i32)
+        Ok(Some(count as i32))
     }
 
-    fn part3(&self, input: &str) -> Option<i32> {
+    fn part3(&self, input: &str) -> PartResult<i32> {
         let mut lines = input.lines();
-        let words = lines
-            .next()
-            .unwrap()
-            .split(':')
-            .nth(1)
-            .unwrap()
-            .split(',')
-            .collect::<Vec<&str>>();
+        let words = parse_words(lines.next())?;
         // skip next line
         lines.next();
 
         // define bool matrix
         let lines: Vec<&str> = lines.collect();
-        let mut mask = vec![vec![false; lines[0].len()]; lines.len()];
+        let Some(width) = lines.first().map(|l| l.len()) else {
+            return Ok(None);
+        };
+        // the vertical check needs a proper rectangle
+        if let Some((y, line)) = lines.iter().enumerate().find(|(_, l)| l.len() != width) {
+            return Err(ParseError::new(
+                y + 3,
+                line.len().min(width) + 1,
+                format!("expected {} runes per line, got {}", width, line.len()),
+            ));
+        }
+        let mut mask = vec![vec![false; width]; lines.len()];
         for (i, line) in lines.iter().enumerate() {
             let rune_mask = get_match_mask(line, &words, true);
             for (j, &x) in rune_mask.iter().enumerate() {
@@ -110,7 +110,8 @@ impl Solution<i32> for Day2 {
             }
         }
         // vertical check
-        for x in 0..lines[0].len() {
+        #[allow(clippy::needless_range_loop)]
+        for x in 0..width {
             let mut line = String::with_capacity(lines.len());
             for str_line in &lines {
                 line.push(str_line.chars().nth(x).unwrap());
@@ -122,7 +123,7 @@ impl Solution<i32> for Day2 {
                 }
             }
         }
-        Some(mask.iter().flatten().filter(|&&x| x).count() as i32)
+        Ok(Some(mask.iter().flatten().filter(|&&x| x).count() as i32))
     }
 }
 
@@ -136,7 +137,7 @@ mod test {
         let input = r#"WORDS:THE,OWE,MES,ROD,HER
 
 AWAKEN THE POWER ADORNED WITH THE FLAMES BRIGHT IRE"#;
-        assert_eq!(day.part1(input), Some(4));
+        assert_eq!(day.part1(input), Ok(Some(4)));
     }
 
     #[test]
@@ -149,7 +150,7 @@ AWAKEN THE POWE ADORNED WITH THE FLAMES BRIGHT IRE
 THE FLAME SHIELDED THE HEART OF THE KINGS
 POWE PO WER P OWE R
 THERE IS THE END"#;
-        assert_eq!(day.part2(input), Some(37));
+        assert_eq!(day.part2(input), Ok(Some(37)));
     }
 
     #[test]
@@ -160,6 +161,27 @@ THERE IS THE END"#;
 HELWORLT
 ENIGWDXL
 TRODEOAL"#;
-        assert_eq!(day.part3(input), Some(10));
+        assert_eq!(day.part3(input), Ok(Some(10)));
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let day = Day2;
+        assert_eq!(
+            day.part1("THE,OWE"),
+            Err(ParseError::new(
+                1,
+                1,
+                "expected `WORDS:` followed by a list of words"
+            ))
+        );
+        let input = r#"WORDS:THE
+
+HELWORLT
+ENIG"#;
+        assert_eq!(
+            day.part3(input),
+            Err(ParseError::new(4, 5, "expected 8 runes per line, got 4"))
+        );
     }
 }
@@ -1,7 +1,38 @@
-use crate::solution::Solution;
+use crate::solution::{ParseError, ParsedSolution, PartResult};
 
 pub struct Day3;
 
+/// returns the grid (1 for earth, 0 for air), its width and height
+fn parse_grid(input: &str) -> Result<(Vec<u16>, usize, usize), ParseError> {
+    let lines = input.lines().collect::<Vec<&str>>();
+    let width = lines.first().map(|l| l.len()).unwrap_or(0);
+    let height = lines.len();
+    let mut grid = vec![0; width * height];
+    for (y, line) in lines.iter().enumerate() {
+        if line.len() != width {
+            return Err(ParseError::new(
+                y + 1,
+                line.len().min(width) + 1,
+                format!("expected {} cells per line, got {}", width, line.len()),
+            ));
+        }
+        for (x, c) in line.chars().enumerate() {
+            match c {
+                '#' => grid[y * width + x] = 1,
+                '.' => (),
+                _ => {
+                    return Err(ParseError::new(
+                        y + 1,
+                        x + 1,
+                        format!("expected `#` or `.`, got `{}`", c),
+                    ))
+                }
+            }
+        }
+    }
+    Ok((grid, width, height))
+}
+
 /// Tries to dig the level provided as depth, return true if successful
 fn carve_deeper(
     grid: &mut [u16],
@@ -60,52 +91,35 @@ fn carve_deeper(
     did_something
 }
 
-impl Solution<i32> for Day3 {
+impl ParsedSolution<i32> for Day3 {
     const DAY: usize = 3;
+    type Parsed<'a> = (Vec<u16>, usize, usize);
 
-    fn part1(&self, input: &str) -> Option<i32> {
-        let lines = input.lines().collect::<Vec<&str>>();
-        let width = lines[0].len();
-        let height = lines.len();
-        let mut grid = vec![0; width * height];
-        for (y, line) in lines.iter().enumerate().take(height) {
-            for x in 0..width {
-                let idx = y * width + x;
-                if line.chars().nth(x).unwrap() == '#' {
-                    grid[idx] = 1;
-                }
-            }
-        }
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Parsed<'a>, ParseError> {
+        parse_grid(input)
+    }
+
+    fn part1(&self, (grid, width, height): &Self::Parsed<'_>) -> PartResult<i32> {
+        let (mut grid, width, height) = (grid.clone(), *width, *height);
         let mut depth = 2;
         while carve_deeper(&mut grid, depth, width, height, false) {
             depth += 1;
         }
-        Some(grid.into_iter().map(|n| n as i32).sum())
+        Ok(Some(grid.into_iter().map(|n| n as i32).sum()))
     }
 
-    fn part2(&self, input: &str) -> Option<i32> {
+    fn part2(&self, parsed: &Self::Parsed<'_>) -> PartResult<i32> {
         // identical to part1
-        self.part1(input)
+        self.part1(parsed)
     }
 
-    fn part3(&self, input: &str) -> Option<i32> {
-        let lines = input.lines().collect::<Vec<&str>>();
-        let width = lines[0].len();
-        let height = lines.len();
-        let mut grid = vec![0; width * height];
-        for (y, line) in lines.iter().enumerate() {
-            for (x, c) in line.chars().enumerate() {
-                let idx = y * width + x;
-                if c == '#' {
-                    grid[idx] = 1;
-                }
-            }
-        }
+    fn part3(&self, (grid, width, height): &Self::Parsed<'_>) -> PartResult<i32> {
+        let (mut grid, width, height) = (grid.clone(), *width, *height);
         let mut depth = 2;
         while carve_deeper(&mut grid, depth, width, height, true) {
             depth += 1;
         }
-        Some(grid.into_iter().map(|n| n as i32).sum())
+        Ok(Some(grid.into_iter().map(|n| n as i32).sum()))
     }
 }
 
@@ -123,7 +137,7 @@ mod test {
 ...####...
 .........."#;
         let day = Day3;
-        assert_eq!(day.part1(input), Some(35));
+        assert_eq!(day.solve(1, input), Ok(Some(35)));
     }
 
     #[test]
@@ -136,6 +150,19 @@ mod test {
 ...####...
 .........."#;
         let day = Day3;
-        assert_eq!(day.part3(input), Some(29));
+        assert_eq!(day.solve(3, input), Ok(Some(29)));
+    }
+
+    #[test]
+    fn test_invalid_grid() {
+        let day = Day3;
+        assert_eq!(
+            day.parse("..#\n.x."),
+            Err(ParseError::new(2, 2, "expected `#` or `.`, got `x`"))
+        );
+        assert_eq!(
+            day.parse("..#\n.."),
+            Err(ParseError::new(2, 3, "expected 3 cells per line, got 2"))
+        );
     }
 }
@@ -1,31 +1,44 @@
 use itertools::Itertools;
 
-use crate::solution::Solution;
+use crate::{
+    solution::{ParseError, ParsedSolution, PartResult},
+    utils::parse::parse_token,
+};
 
 pub struct Day4;
 
-impl Solution<i32> for Day4 {
+fn parse_nails(input: &str) -> Result<Vec<i32>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, l)| parse_token(i + 1, l, l))
+        .collect()
+}
+
+impl ParsedSolution<i32> for Day4 {
     const DAY: usize = 4;
+    type Parsed<'a> = Vec<i32>;
 
-    fn part1(&self, input: &str) -> Option<i32> {
-        let nums = input
-            .lines()
-            .map(|l| l.parse::<i32>().unwrap())
-            .collect::<Vec<_>>();
-        let min = *nums.iter().min().unwrap();
-        Some(nums.into_iter().map(|n| n - min).sum())
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Parsed<'a>, ParseError> {
+        parse_nails(input)
     }
 
-    fn part2(&self, input: &str) -> Option<i32> {
-        self.part1(input)
+    fn part1(&self, nums: &Self::Parsed<'_>) -> PartResult<i32> {
+        let Some(min) = nums.iter().min().copied() else {
+            return Ok(None);
+        };
+        Ok(Some(nums.iter().map(|n| n - min).sum()))
     }
 
-    fn part3(&self, input: &str) -> Option<i32> {
-        let nums = input
-            .lines()
-            .map(|l| l.parse::<i32>().unwrap())
-            .sorted()
-            .collect::<Vec<_>>();
+    fn part2(&self, nums: &Self::Parsed<'_>) -> PartResult<i32> {
+        self.part1(nums)
+    }
+
+    fn part3(&self, nums: &Self::Parsed<'_>) -> PartResult<i32> {
+        let nums = nums.iter().copied().sorted().collect::<Vec<_>>();
+        if nums.is_empty() {
+            return Ok(None);
+        }
 
         let mut min;
         let median = nums[nums.len() / 2];
@@ -66,7 +79,7 @@ impl Solution<i32> for Day4 {
             min = at_median;
         }
 
-        Some(min)
+        Ok(Some(min))
     }
 }
 
@@ -82,6 +95,16 @@ mod test {
 5
 6
 8"#;
-        assert_eq!(d4.part3(input), Some(8))
+        assert_eq!(d4.solve(3, input), Ok(Some(8)))
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let d4 = Day4;
+        assert_eq!(
+            d4.parse("3\n4a\n5"),
+            Err(ParseError::new(2, 1, "expected i32, got `4a`"))
+        );
+        assert_eq!(d4.solve(1, ""), Ok(None));
     }
 }
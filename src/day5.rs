@@ -4,8 +4,11 @@ use std::{
     str::FromStr,
 };
 
-use crate::solution::Solution;
-use crate::utils::cycle_finder::CycleFinderExt;
+use crate::solution::{ParseError, PartResult, Solution};
+use crate::utils::{
+    cycle_finder::CycleFinderExt,
+    parse::{column_of, parse_token},
+};
 pub struct Day5;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -51,7 +54,7 @@ impl Day5World {
 }
 
 impl FromStr for Day5World {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut columns = [
@@ -60,12 +63,29 @@ impl FromStr for Day5World {
             VecDeque::new(),
             VecDeque::new(),
         ];
-        for line in s.lines() {
-            let mut clappers = line.split_whitespace().map(|s| s.parse().unwrap());
+        for (i, line) in s.lines().enumerate() {
+            let mut clappers = line.split_whitespace();
             for column in &mut columns {
-                column.push_back(clappers.next().unwrap());
+                let clapper = clappers.next().ok_or_else(|| {
+                    ParseError::new(i + 1, line.len() + 1, "expected 4 clappers per line")
+                })?;
+                column.push_back(parse_token(i + 1, line, clapper)?);
+            }
+            if let Some(extra) = clappers.next() {
+                return Err(ParseError::new(
+                    i + 1,
+                    column_of(line, extra),
+                    "expected 4 clappers per line",
+                ));
             }
         }
+        if columns[0].is_empty() {
+            return Err(ParseError::new(
+                1,
+                1,
+                "expected at least one row of clappers",
+            ));
+        }
 
         Ok(Self {
             columns,
@@ -110,8 +130,8 @@ impl Iterator for Day5World {
 impl Solution<String> for Day5 {
     const DAY: usize = 5;
 
-    fn part1(&self, input: &str) -> Option<String> {
-        let mut world = input.parse::<Day5World>().unwrap();
+    fn part1(&self, input: &str) -> PartResult<String> {
+        let mut world = input.parse::<Day5World>()?;
         let mut res = String::new();
         for _i in 0..10 {
             res = world.dance();
@@ -119,11 +139,11 @@ impl Solution<String> for Day5 {
             //println!("{}", self);
         }
 
-        Some(res)
+        Ok(Some(res))
     }
 
-    fn part2(&self, input: &str) -> Option<String> {
-        let mut world = input.parse::<Day5World>().unwrap();
+    fn part2(&self, input: &str) -> PartResult<String> {
+        let mut world = input.parse::<Day5World>()?;
 
         let mut shout_counts = HashMap::new();
         let mut round: u128 = 1;
@@ -138,23 +158,23 @@ impl Solution<String> for Day5 {
                 // shouted should be a number
                 let shouted = shouted.parse::<u128>().unwrap();
                 let solution = shouted * round;
-                return Some(solution.to_string());
+                return Ok(Some(solution.to_string()));
             }
             round += 1;
         }
     }
 
-    fn part3(&self, input: &str) -> Option<String> {
+    fn part3(&self, input: &str) -> PartResult<String> {
         // an iterator returning a clone of itself on each iteration is not great. But this seemed like the quickest way to get the cycle finder to work, instead of just loopin 10_000_000 times
-        let mut world = input.parse::<Day5World>().unwrap();
+        let world = input.parse::<Day5World>()?;
         let mut max_shouted = 0;
-        for (shouted, world) in world.find_cycle() {
+        for (shouted, _world) in world.find_cycle() {
             let shouted = shouted.parse::<u128>().unwrap();
             if shouted > max_shouted {
                 max_shouted = shouted;
             }
         }
-        Some(max_shouted.to_string())
+        Ok(Some(max_shouted.to_string()))
     }
 }
 
@@ -174,7 +194,7 @@ mod test {
 5 2 3 4"#;
 
         let solution = get_solution();
-        assert_eq!(solution.part1(test_input), Some("2323".to_string()));
+        assert_eq!(solution.part1(test_input), Ok(Some("2323".to_string())));
     }
 
     #[test]
@@ -182,7 +202,24 @@ mod test {
         let test_input = r#"2 3 4 5
 6 7 8 9"#;
         let solution = get_solution();
-        assert_eq!(solution.part2(test_input), Some("50877075".to_string()));
+        assert_eq!(solution.part2(test_input), Ok(Some("50877075".to_string())));
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let solution = get_solution();
+        assert_eq!(
+            solution.part1("2 3 4 5\n6 7 8"),
+            Err(ParseError::new(2, 6, "expected 4 clappers per line"))
+        );
+        assert_eq!(
+            solution.part1("2 3 4 5\n6 7 B 9"),
+            Err(ParseError::new(2, 5, "expected usize, got `B`"))
+        );
+        assert_eq!(
+            solution.part1("2 3 4 5\n6 7 8 9 10"),
+            Err(ParseError::new(2, 9, "expected 4 clappers per line"))
+        );
     }
 
     /*#[test]
@@ -190,6 +227,6 @@ mod test {
             let test_input = r#"2 3 4 5
     6 7 8 9"#;
             let mut solution = get_solution();
-            assert_eq!(solution.part3(test_input), Some("6584".to_string()));
+            assert_eq!(solution.part3(test_input), Ok(Some("6584".to_string())));
         }*/
 }
@@ -3,7 +3,10 @@ use std::collections::{HashMap, HashSet};
 use itertools::Itertools;
 
 // Template for new days
-use crate::solution::Solution;
+use crate::{
+    log::{debug, trace},
+    solution::{ParseError, PartResult, Solution},
+};
 
 pub struct Day6;
 
@@ -15,17 +18,17 @@ struct TreeNode<'a> {
     parent: Option<&'a str>,
 }
 
-impl<'a> From<&'a str> for TreeNode<'a> {
-    fn from(s: &'a str) -> Self {
-        // gets a single line
-        let mut parts = s.split(':');
-        let name = parts.next().unwrap();
-        let children = parts.next().unwrap().split(',').collect();
-        TreeNode {
+impl<'a> TreeNode<'a> {
+    /// parses a single line, line_nr is only used for errors
+    fn parse(line_nr: usize, s: &'a str) -> Result<Self, ParseError> {
+        let (name, children) = s.split_once(':').ok_or_else(|| {
+            ParseError::new(line_nr, s.len() + 1, "expected `name:child,child,...`")
+        })?;
+        Ok(TreeNode {
             name,
-            children,
+            children: children.split(',').collect(),
             parent: None,
-        }
+        })
     }
 }
 
@@ -117,13 +120,15 @@ impl<'a> Day6World<'a> {
                     }
                 }
             } else {
-                //println!("Ant or Bug: {:?}", node);
+                trace!("Ant or Bug: {:?}", node);
             }
         }
 
-        // print paths, sorted by length
-        for (len, paths) in paths.iter().sorted_by_key(|(len, _)| *len) {
-            println!("{}: {} paths", len, paths.len());
+        // log paths, sorted by length
+        if crate::log::enabled(crate::log::Level::Debug) {
+            for (len, paths) in paths.iter().sorted_by_key(|(len, _)| *len) {
+                debug!("{}: {} paths", len, paths.len());
+            }
         }
         // find entry with exactly one path
         paths
@@ -133,40 +138,36 @@ impl<'a> Day6World<'a> {
     }
 }
 
-impl<'a> From<&'a str> for Day6World<'a> {
-    fn from(s: &'a str) -> Self {
+impl<'a> TryFrom<&'a str> for Day6World<'a> {
+    type Error = ParseError;
+
+    fn try_from(s: &'a str) -> Result<Self, Self::Error> {
         let mut world = Day6World::new();
-        for line in s.lines() {
-            let node: TreeNode = line.into();
+        for (i, line) in s.lines().enumerate() {
+            let node = TreeNode::parse(i + 1, line)?;
             world.tree.insert(node.name, node);
         }
         world.populate_parents();
-        world
+        Ok(world)
     }
 }
 
 impl Solution<String> for Day6 {
     const DAY: usize = 6;
 
-    fn part1(&self, input: &str) -> Option<String> {
-        let world: Day6World<'_> = input.into();
+    fn part1(&self, input: &str) -> PartResult<String> {
+        let world = Day6World::try_from(input)?;
         let solution = world.bfs_find_singular_solution("RR");
-        Some(solution.unwrap().join(""))
+        Ok(solution.map(|path| path.join("")))
     }
 
-    fn part2(&self, input: &str) -> Option<String> {
-        let world: Day6World<'_> = input.into();
+    fn part2(&self, input: &str) -> PartResult<String> {
+        let world = Day6World::try_from(input)?;
         let final_path = world.bfs_find_singular_solution("RR");
-        Some(
-            final_path
-                .unwrap()
-                .into_iter()
-                .map(|s| s.chars().next().unwrap())
-                .join(""),
-        )
+        Ok(final_path.map(|path| path.into_iter().filter_map(|s| s.chars().next()).join("")))
     }
 
-    fn part3(&self, input: &str) -> Option<String> {
+    fn part3(&self, input: &str) -> PartResult<String> {
         self.part2(input)
     }
 }
@@ -191,6 +192,15 @@ F:@
 G:@
 H:@"#;
         let solution = get_solution();
-        assert_eq!(solution.part1(test_input), Some("RRB@".to_string()));
+        assert_eq!(solution.part1(test_input), Ok(Some("RRB@".to_string())));
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let solution = get_solution();
+        assert_eq!(
+            solution.part1("RR:A,B\nA:@\nB"),
+            Err(ParseError::new(3, 2, "expected `name:child,child,...`"))
+        );
     }
 }
@@ -3,7 +3,11 @@ use std::{collections::HashSet, str::FromStr};
 use itertools::Itertools;
 
 // Template for new days
-use crate::solution::Solution;
+use crate::{
+    log::debug,
+    solution::{ParseError, ParsedSolution, PartResult},
+    utils::parse::column_of,
+};
 
 pub struct Day7;
 
@@ -117,17 +121,19 @@ enum Instruction {
     Maintain,
 }
 
-impl From<char> for Instruction {
-    fn from(c: char) -> Self {
+impl Instruction {
+    fn from_char(c: char) -> Option<Self> {
         match c {
-            '+' => Self::AddOne,
-            '-' => Self::SubtractOne,
-            '=' => Self::Maintain,
-            'S' => Self::Maintain,
-            _ => panic!("Invalid instruction"),
+            '+' => Some(Self::AddOne),
+            '-' => Some(Self::SubtractOne),
+            '=' => Some(Self::Maintain),
+            'S' => Some(Self::Maintain),
+            _ => None,
         }
     }
 }
+
+#[derive(Clone)]
 struct Chariot {
     pub name: String,
     pub power: usize,
@@ -148,27 +154,36 @@ impl Chariot {
     }
 }
 
-impl FromStr for Chariot {
-    type Err = ();
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut parts = s.split(':');
-        let name = parts.next().unwrap();
-        let plan = parts.next().unwrap().split(',').map(|s| match s {
-            "+" => Instruction::AddOne,
-            "-" => Instruction::SubtractOne,
-            "=" => Instruction::Maintain,
-            _ => panic!("Invalid instruction"),
-        });
+impl Chariot {
+    /// parses a line like `A:+,-,=`, line_nr is only used for errors
+    fn parse(line_nr: usize, s: &str) -> Result<Self, ParseError> {
+        let (name, plan) = s.split_once(':').ok_or_else(|| {
+            ParseError::new(line_nr, s.len() + 1, "expected `name:` followed by a plan")
+        })?;
+        let plan = plan
+            .split(',')
+            .map(|action| match action {
+                "+" => Ok(Instruction::AddOne),
+                "-" => Ok(Instruction::SubtractOne),
+                "=" => Ok(Instruction::Maintain),
+                _ => Err(ParseError::new(
+                    line_nr,
+                    column_of(s, action),
+                    format!("invalid instruction `{}`", action),
+                )),
+            })
+            .collect::<Result<_, _>>()?;
         Ok(Self {
             name: name.to_string(),
             power: 10,
             essence_collected: 0,
             instruction_idx: 0,
-            plan: plan.collect(),
+            plan,
         })
     }
 }
 
+#[derive(Clone)]
 struct Day7World {
     pub chariots: Vec<Chariot>,
     current_segment: usize,
@@ -209,21 +224,41 @@ impl Day7World {
         }
     }
 
-    fn track_from_ascii(ascii_track: &str) -> Vec<Instruction> {
+    /// first_line is the line number of the track in the whole input, for errors
+    fn track_from_ascii(
+        ascii_track: &str,
+        first_line: usize,
+    ) -> Result<Vec<Instruction>, ParseError> {
         // first, we collect the chars into a vec (for easier indexing) and determine the dimensions of this 2d grid
         let mut instructions: Vec<Option<Instruction>> = Vec::with_capacity(ascii_track.len());
-        let width = ascii_track.lines().map(|l| l.len()).max().unwrap();
+        let width = ascii_track.lines().map(|l| l.len()).max().unwrap_or(0);
         let mut height = 0;
         let mut track = Vec::new();
-        for line in ascii_track.lines() {
+        for (i, line) in ascii_track.lines().enumerate() {
             height += 1;
             let to_pad = width - line.len();
-            instructions.extend(line.chars().map(|c| match c {
-                ' ' => None,
-                _ => Some(c.into()),
-            }));
-            instructions.extend(std::iter::repeat(None).take(to_pad));
+            for (x, c) in line.chars().enumerate() {
+                let instruction = match c {
+                    ' ' => None,
+                    _ => Some(Instruction::from_char(c).ok_or_else(|| {
+                        ParseError::new(
+                            first_line + i,
+                            x + 1,
+                            format!("invalid track segment `{}`", c),
+                        )
+                    })?),
+                };
+                instructions.push(instruction);
+            }
+            instructions.extend(std::iter::repeat_n(None, to_pad));
+        }
+        if width < 2 {
+            return Err(ParseError::new(first_line, 1, "track is too small"));
         }
+        // converts our coordinates back into a position in the input
+        let error_at = |(x, y): (usize, usize), message: &str| {
+            ParseError::new(first_line + height - y - 1, x + 1, message)
+        };
         let vec_idx = |(x, y): (usize, usize)| -> usize { ((height - y - 1) * width) + x };
         let get_next_pos = |(x, y): (usize, usize), dir: Direction| -> Option<(usize, usize)> {
             dir.try_march(x, y, width, height)
@@ -235,7 +270,9 @@ impl Day7World {
 
         while current_pos != (0, height - 1) {
             let idx = vec_idx(current_pos);
-            track.push(instructions[idx].unwrap());
+            let instruction =
+                instructions[idx].ok_or_else(|| error_at(current_pos, "track is not connected"))?;
+            track.push(instruction);
             // if possible, we turn right
             let next_dir = current_dir.rotate_clockwise();
             let next_pos = get_next_pos(current_pos, next_dir);
@@ -259,36 +296,38 @@ impl Day7World {
             // finally, we turn left
             let next_dir = current_dir.rotate_counterclockwise();
             let next_pos = get_next_pos(current_pos, next_dir);
-            if let Some(next_pos) = next_pos {
-                let idx = vec_idx(next_pos);
-                if instructions[idx].is_some() {
+            match next_pos {
+                Some(next_pos) if instructions[vec_idx(next_pos)].is_some() => {
                     current_dir = next_dir;
                     current_pos = next_pos;
-                    continue;
-                } else {
-                    panic!("No way out at {:?}", current_pos);
                 }
+                _ => return Err(error_at(current_pos, "no way out")),
             }
         }
         // lastly, add the start
-        track.push('S'.into());
+        track.push(Instruction::Maintain);
 
-        track
+        Ok(track)
     }
 }
 
 impl FromStr for Day7World {
-    type Err = ();
+    type Err = ParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut split = s.split("\n\n");
-        let chariots = split
-            .next()
-            .unwrap()
+        let chariot_lines = split.next().unwrap_or_default();
+        let chariots = chariot_lines
             .lines()
-            .map(|l| l.parse().unwrap())
-            .collect();
+            .enumerate()
+            .map(|(i, l)| Chariot::parse(i + 1, l))
+            .collect::<Result<_, _>>()?;
 
-        let track = split.next().map(Self::track_from_ascii);
+        // the track starts after the chariots and an empty line
+        let first_track_line = chariot_lines.lines().count() + 2;
+        let track = split
+            .next()
+            .map(|track| Self::track_from_ascii(track, first_track_line))
+            .transpose()?;
         Ok(Self {
             chariots,
             current_segment: 0,
@@ -297,11 +336,34 @@ impl FromStr for Day7World {
     }
 }
 
-impl Solution<String> for Day7 {
+/// The input is kept around for the position of the missing track error
+pub struct Day7Input<'a> {
+    world: Day7World,
+    input: &'a str,
+}
+
+/// parts 2 and 3 need a track, it would be after the end of the input
+fn missing_track(input: &str) -> ParseError {
+    ParseError::new(
+        input.lines().count() + 1,
+        1,
+        "expected a track after an empty line",
+    )
+}
+
+impl ParsedSolution<String> for Day7 {
     const DAY: usize = 7;
+    type Parsed<'a> = Day7Input<'a>;
 
-    fn part1(&self, input: &str) -> Option<String> {
-        let mut world = input.parse::<Day7World>().unwrap();
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Parsed<'a>, ParseError> {
+        Ok(Day7Input {
+            world: input.parse()?,
+            input,
+        })
+    }
+
+    fn part1(&self, parsed: &Self::Parsed<'_>) -> PartResult<String> {
+        let mut world = parsed.world.clone();
         world.run_steps(10);
         let result = world
             .chariots
@@ -309,11 +371,14 @@ impl Solution<String> for Day7 {
             .map(|c| (c.name.clone(), c.essence_collected))
             .sorted_by(|a, b| b.1.cmp(&a.1))
             .collect_vec();
-        Some(result.into_iter().map(|(name, _)| name).join(""))
+        Ok(Some(result.into_iter().map(|(name, _)| name).join("")))
     }
 
-    fn part2(&self, input: &str) -> Option<String> {
-        let mut world = input.parse::<Day7World>().unwrap();
+    fn part2(&self, parsed: &Self::Parsed<'_>) -> PartResult<String> {
+        if parsed.world.track.is_none() {
+            return Err(missing_track(parsed.input));
+        }
+        let mut world = parsed.world.clone();
         for _ in 0..10 {
             world.run_loop();
         }
@@ -323,11 +388,14 @@ impl Solution<String> for Day7 {
             .map(|c| (c.name.clone(), c.essence_collected))
             .sorted_by(|a, b| b.1.cmp(&a.1))
             .collect_vec();
-        Some(result.into_iter().map(|(name, _)| name).join(""))
+        Ok(Some(result.into_iter().map(|(name, _)| name).join("")))
     }
 
-    fn part3(&self, input: &str) -> Option<String> {
-        let mut world = input.parse::<Day7World>().unwrap();
+    fn part3(&self, parsed: &Self::Parsed<'_>) -> PartResult<String> {
+        if parsed.world.track.is_none() {
+            return Err(missing_track(parsed.input));
+        }
+        let mut world = parsed.world.clone();
         world
             .chariots
             .extend(generate_possible_plans().map(|plan| Chariot {
@@ -338,19 +406,21 @@ impl Solution<String> for Day7 {
                 plan,
             }));
 
-        println!("Starting loop. Total chariots: {}", world.chariots.len());
+        debug!("Starting loop. Total chariots: {}", world.chariots.len());
         for _ in 0..2024 {
             world.run_loop();
         }
-        println!("Loop done");
+        debug!("Loop done");
         // how many did the first strategy collect?
-        let to_beat = world.chariots[0].essence_collected;
+        let Some(to_beat) = world.chariots.first().map(|c| c.essence_collected) else {
+            return Ok(None);
+        };
         let strats_beating_1 = world
             .chariots
             .iter()
             .filter(|c| c.essence_collected > to_beat)
             .count();
-        Some(strats_beating_1.to_string())
+        Ok(Some(strats_beating_1.to_string()))
     }
 }
 
@@ -358,7 +428,7 @@ impl Solution<String> for Day7 {
 mod test {
     use super::*;
 
-    fn get_solution() -> impl Solution<String> {
+    fn get_solution() -> impl ParsedSolution<String> {
         Day7
     }
 
@@ -369,7 +439,7 @@ B:+,=,-,+
 C:=,-,+,+
 D:=,=,=,+"#;
         let solution = get_solution();
-        assert_eq!(solution.part1(test_input), Some("BDCA".to_string()));
+        assert_eq!(solution.solve(1, test_input), Ok(Some("BDCA".to_string())));
     }
 
     #[test]
@@ -383,12 +453,37 @@ D:=,=,=,+
 S+===
 -   +
 =+=-+"#;
-        assert_eq!(solution.part2(test_input), Some("DCBA".to_string()));
+        assert_eq!(solution.solve(2, test_input), Ok(Some("DCBA".to_string())));
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let solution = get_solution();
+        assert_eq!(
+            solution.solve(1, "A:+,-\nB:+,x,="),
+            Err(ParseError::new(2, 5, "invalid instruction `x`"))
+        );
+        assert_eq!(
+            solution.solve(2, "A:+,-"),
+            Err(ParseError::new(
+                2,
+                1,
+                "expected a track after an empty line"
+            ))
+        );
+        assert_eq!(
+            solution.solve(2, "A:+,-\n\nS+=\n-x+\n=+="),
+            Err(ParseError::new(4, 2, "invalid track segment `x`"))
+        );
+        assert_eq!(
+            solution.solve(2, "A:+,-\n\nS+=\n- +\n= ="),
+            Err(ParseError::new(5, 3, "no way out"))
+        );
     }
 
     #[test]
     fn test_part3() {
-        let test_input = r#"A:=,+,=,+,+,-,+,=,+,-,-
+        let _test_input = r#"A:=,+,=,+,+,-,+,=,+,-,-
 
 S+= +=-== +=++=     =+=+=--=    =-= ++=     +=-  =+=++=-+==+ =++=-=-=--
 - + +   + =   =     =      =   == = - -     - =  =         =-=        -
@@ -400,7 +495,7 @@ S+= +=-== +=++=     =+=+=--=    =-= ++=     +=-  =+=++=-+==+ =++=-=-=--
 -               = = = =   +  +  ==+ = = +   =        ++    =          -
 -               = + + =   +  -  = + = = +   =        +     =          -
 --==++++==+=+++-= =-= =-+-=  =+-= =-= =--   +=++=+++==     -=+=++==+++-"#;
-        let solution = get_solution();
-        //assert_eq!(solution.part3(test_input), None);
+        let _solution = get_solution();
+        //assert_eq!(solution.solve(3, test_input), None);
     }
 }
@@ -1,5 +1,8 @@
 // Template for new days
-use crate::solution::Solution;
+use crate::{
+    solution::{PartResult, Solution},
+    utils::parse::parse_token,
+};
 
 pub struct Day8;
 
@@ -24,6 +27,8 @@ impl Pyramid {
     pub fn add_row(&mut self) {
         let next_row = self.rows[self.rows.len() - 1] + 2;
         self.sum += next_row;
+        self.column_heights.push(0);
+        self.column_heights.iter_mut().for_each(|x| *x += 1);
         self.rows.push(next_row);
     }
 
@@ -34,7 +39,7 @@ impl Pyramid {
         self.sum += next_thickness * next_width;
         self.last_thickness = next_thickness;
         self.rows
-            .extend(std::iter::repeat(next_width).take(next_thickness as usize));
+            .extend(std::iter::repeat_n(next_width, next_thickness as usize));
     }
 
     pub fn add_row_p3(&mut self, priests: usize) {
@@ -48,15 +53,15 @@ impl Pyramid {
             .iter_mut()
             .for_each(|x| *x += next_thickness);
         self.rows
-            .extend(std::iter::repeat(next_width).take(next_thickness as usize));
+            .extend(std::iter::repeat_n(next_width, next_thickness as usize));
     }
 
     pub fn column_heights(&self) -> Vec<u128> {
         let width = self.rows[self.rows.len() - 1] as usize;
-        let mut columnwise = Vec::with_capacity(width as usize);
+        let mut columnwise = Vec::with_capacity(width);
         let half_width = width / 2;
         for i in 0..width {
-            let idx = (i as i32 - half_width as i32).abs() as usize;
+            let idx = i.abs_diff(half_width);
             columnwise.push(self.column_heights[idx])
         }
         columnwise
@@ -69,31 +74,30 @@ impl Pyramid {
         if columnwise.len() == 1 {
             return 0;
         }
-        let correct = columnwise
+        columnwise
             .into_iter()
             .skip(1) // outermost blocks are always filled
             .take(base_width as usize - 2)
-            .fold(0, |acc, x| ((first_line * x) % 10) + acc);
-        correct
+            .fold(0, |acc, x| ((first_line * x) % 10) + acc)
     }
 }
 
 impl Solution<u128> for Day8 {
     const DAY: usize = 8;
 
-    fn part1(&self, input: &str) -> Option<u128> {
-        let parsed: u128 = input.parse().unwrap();
+    fn part1(&self, input: &str) -> PartResult<u128> {
+        let parsed: u128 = parse_token(1, input, input.trim())?;
         let mut pyramid = Pyramid::new();
         while pyramid.sum < parsed {
             pyramid.add_row();
         }
         let diff = pyramid.sum - parsed;
         let width = pyramid.rows[pyramid.rows.len() - 1];
-        Some(width * diff)
+        Ok(Some(width * diff))
     }
 
-    fn part2(&self, input: &str) -> Option<u128> {
-        let parsed: usize = input.parse().unwrap();
+    fn part2(&self, input: &str) -> PartResult<u128> {
+        let parsed: usize = parse_token(1, input, input.trim())?;
         let mut pyramid = Pyramid::new();
         let blocks = 20240000;
         while pyramid.sum < blocks {
@@ -101,11 +105,11 @@ impl Solution<u128> for Day8 {
         }
         let diff = pyramid.sum - blocks;
         let width = pyramid.rows[pyramid.rows.len() - 1];
-        return Some(width * diff);
+        Ok(Some(width * diff))
     }
 
-    fn part3(&self, input: &str) -> Option<u128> {
-        let parsed: usize = input.parse().unwrap();
+    fn part3(&self, input: &str) -> PartResult<u128> {
+        let parsed: usize = parse_token(1, input, input.trim())?;
         let mut pyramid = Pyramid::new();
         let blocks = 202400000;
 
@@ -113,7 +117,7 @@ impl Solution<u128> for Day8 {
             pyramid.add_row_p3(parsed);
         }
         let diff = pyramid.sum - blocks - pyramid.empty_blocks(parsed);
-        return Some(diff);
+        Ok(Some(diff))
     }
 }
 
@@ -129,26 +133,26 @@ mod test {
     fn test_part1() {
         let test_input = r#"13"#;
         let solution = get_solution();
-        assert_eq!(solution.part1(test_input), Some(21));
+        assert_eq!(solution.part1(test_input), Ok(Some(21)));
         let mut pyramid = Pyramid::new();
         while pyramid.sum < 13 {
             pyramid.add_row();
         }
         let columnwise = pyramid.column_heights();
-        //assert_eq!(columnwise, vec![1, 2, 3, 4, 3, 2, 1]);
+        assert_eq!(columnwise, vec![1, 2, 3, 4, 3, 2, 1]);
     }
 
     #[test]
     fn test_part2() {
-        let solution = get_solution();
-        let test_input = r#"3"#;
-        //assert_eq!(solution.part2(test_input), Some(27));
+        let _solution = get_solution();
+        let _test_input = r#"3"#;
+        //assert_eq!(solution.part2(test_input), Ok(Some(27)));
     }
 
     #[test]
     fn test_part3() {
-        let solution = get_solution();
-        let test_input = r#"2"#;
-        //assert_eq!(solution.part3(test_input), Some(2));
+        let _solution = get_solution();
+        let _test_input = r#"2"#;
+        //assert_eq!(solution.part3(test_input), Ok(Some(2)));
     }
 }
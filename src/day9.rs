@@ -1,83 +1,59 @@
-use std::collections::{HashMap, HashSet};
-
 use itertools::Itertools;
 
 // Template for new days
 use crate::{
-    solution::Solution,
-    utils::bfs::{bfs, Graph},
+    log::trace,
+    solution::{ParseError, PartResult, Solution},
+    utils::{
+        bfs::{bfs, bfs_map, BfsMap, Graph},
+        parse::parse_token,
+    },
 };
 
 pub struct Day9;
 
-/// let's iteratively create a lookup table just to make sure we catch the correct solution
-/// returns beetle count
+/// the two sparkballs may differ by at most this much brightness
+const MAX_BALL_DIFFERENCE: i64 = 100;
+
+/// brightest single sparkball any split of brightness can need
+fn brightest_ball(brightness: i64) -> i64 {
+    (brightness + MAX_BALL_DIFFERENCE) / 2
+}
+
+/// beetle counts of every brightness up to a limit, from one bfs starting at 0
 struct BeetleCountCalculator {
-    memo: HashMap<i64, i64>,
-    current_iterations: i64,
-    fully_filled_until: i64,
+    counts: BfsMap<i64>,
 }
 
 impl BeetleCountCalculator {
-    pub fn new() -> Self {
+    pub fn new(max_brightness: i64, stamps: &[i64]) -> Self {
+        let mut stamper = BfsBeetlesStamper {
+            stamps: stamps.to_vec(),
+            max_brightness,
+        };
         Self {
-            memo: HashMap::from([(0, 0)]),
-            current_iterations: 0,
-            fully_filled_until: 0,
+            counts: bfs_map(&mut stamper, 0, None),
         }
     }
 
-    pub fn get(&mut self, brightness: i64, stamps: &[i64]) -> i64 {
-        while !self.memo.contains_key(&brightness) {
-            let result = minimum_stamps_bfs(brightness, stamps);
-            self.memo.insert(brightness, result);
-            return result;
-        }
-        *self.memo.get(&brightness).unwrap()
+    pub fn get(&self, brightness: i64) -> i64 {
+        self.counts
+            .distance(&brightness)
+            .unwrap_or_else(|| panic!("brightness {brightness} was not precomputed")) as i64
     }
 
-    /// yeah yeah, this is an explosion of combinations in later iterations and we do this for all keys
-    /// sue me
-    fn new_iteration(&mut self, stamps: &[i64]) {
-        let mut new_beetle_counts = Vec::new();
-        for key in self
-            .memo
-            .keys()
-            .filter(|key| **key > self.fully_filled_until)
-        {
-            new_beetle_counts.extend(
-                stamps
-                    .iter()
-                    .map(|stamp| stamp + key)
-                    .filter(|new_value| !self.memo.contains_key(new_value)),
-            );
-        }
-        // all of the beetle counts should be the shortest possible path because we built them up from smallest paths
-        self.memo.extend(
-            new_beetle_counts
-                .into_iter()
-                .map(|num| (num, self.current_iterations + 1)),
-        );
-        // update fully_filled_until
-        while self.memo.contains_key(&(self.fully_filled_until + 1)) {
-            self.fully_filled_until += 1;
-        }
-        self.current_iterations += 1;
-    }
-
-    pub fn sparkball_combinations(&mut self, brightness: i64, stamps: &[i64]) -> i64 {
+    pub fn sparkball_combinations(&self, brightness: i64) -> i64 {
         let half_brightness = brightness / 2;
         // iterator based approach with nested closure did not work, so let's use for loops for now
-        // at least this inefficient solutions should work okay once the values are precomputed
         let mut min = i64::MAX;
-        for a in (half_brightness - 50)..=(half_brightness) {
+        for a in (half_brightness - MAX_BALL_DIFFERENCE / 2)..=(half_brightness) {
             let b = brightness - a;
-            if (b - a).abs() > 100 {
+            if (b - a).abs() > MAX_BALL_DIFFERENCE {
                 continue;
             }
-            let sum = self.get(a, stamps) + self.get(b, stamps);
+            let sum = self.get(a) + self.get(b);
             if sum < min {
-                println!("Better combination: {a} and {b}: {sum}");
+                trace!("Better combination: {a} and {b}: {sum}");
                 min = sum;
             }
         }
@@ -86,6 +62,7 @@ impl BeetleCountCalculator {
 }
 
 /// this greedy algo works for part 1 but not 2
+#[allow(dead_code)]
 fn minimum_stamps(goal: i64, stamps: &[i64]) -> Vec<i64> {
     let mut times_stamped = vec![0; stamps.len()];
     let mut remaining = goal;
@@ -100,7 +77,7 @@ fn minimum_stamps(goal: i64, stamps: &[i64]) -> Vec<i64> {
 
 fn minimum_stamps_bfs(goal: i64, stamps: &[i64]) -> i64 {
     let mut minimizer = BfsBeetlesMinimizer {
-        stamps: stamps.iter().cloned().collect(),
+        stamps: stamps.to_vec(),
     };
     let path = bfs(&mut minimizer, goal, 0).unwrap().collect_vec();
     path.iter().tuple_windows().map(|(a, b)| a - b).len() as i64
@@ -116,53 +93,78 @@ impl Graph for BfsBeetlesMinimizer {
     type Node = i64;
 
     fn neighbors(&mut self, n: &Self::Node) -> impl Iterator<Item = Self::Node> {
-        let n = n.clone();
+        let n = *n;
         self.stamps
             .iter()
             .filter_map(move |stamp| if *stamp <= n { Some(n - stamp) } else { None })
     }
 }
 
+/// counts up from 0 instead, so one bfs covers every brightness
+#[derive(Debug)]
+struct BfsBeetlesStamper {
+    stamps: Vec<i64>,
+    max_brightness: i64,
+}
+
+impl Graph for BfsBeetlesStamper {
+    /// brightness so far
+    type Node = i64;
+
+    fn neighbors(&mut self, n: &Self::Node) -> impl Iterator<Item = Self::Node> {
+        let n = *n;
+        self.stamps
+            .iter()
+            .map(move |stamp| n + stamp)
+            .filter(|next| *next <= self.max_brightness)
+    }
+}
+
+fn parse_brightnesses(input: &str) -> Result<Vec<i64>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, l)| parse_token(i + 1, l, l))
+        .collect()
+}
+
 impl Solution<i64> for Day9 {
     const DAY: usize = 9;
 
-    fn part1(&self, input: &str) -> Option<i64> {
-        Some(
-            input
-                .lines()
-                .map(|s| minimum_stamps_bfs(s.parse().unwrap(), &[10, 5, 3, 1]))
+    fn part1(&self, input: &str) -> PartResult<i64> {
+        Ok(Some(
+            parse_brightnesses(input)?
+                .into_iter()
+                .map(|b| minimum_stamps_bfs(b, &[10, 5, 3, 1]))
                 .sum(),
-        )
+        ))
     }
 
-    fn part2(&self, input: &str) -> Option<i64> {
-        let mut calc = BeetleCountCalculator::new();
-        Some(
-            input
-                .lines()
-                .map(|s| {
-                    minimum_stamps_bfs(s.parse().unwrap(), &[1, 3, 5, 10, 15, 16, 20, 24, 25, 30])
-                })
+    fn part2(&self, input: &str) -> PartResult<i64> {
+        Ok(Some(
+            parse_brightnesses(input)?
+                .into_iter()
+                .map(|b| minimum_stamps_bfs(b, &[1, 3, 5, 10, 15, 16, 20, 24, 25, 30]))
                 .sum(),
-        )
+        ))
     }
 
-    fn part3(&self, input: &str) -> Option<i64> {
-        let mut calc = BeetleCountCalculator::new();
-        Some(
-            input
-                .lines()
-                .map(|s| {
-                    calc.sparkball_combinations(
-                        s.parse().unwrap(),
-                        &[
-                            1, 3, 5, 10, 15, 16, 20, 24, 25, 30, 37, 38, 49, 50, 74, 75, 100, 101,
-                        ],
-                    )
-                })
-                .inspect(|res| println!("{:?}", res))
+    fn part3(&self, input: &str) -> PartResult<i64> {
+        let brightnesses = parse_brightnesses(input)?;
+        let max_brightness = brightnesses.iter().max().map_or(0, |b| brightest_ball(*b));
+        let calc = BeetleCountCalculator::new(
+            max_brightness,
+            &[
+                1, 3, 5, 10, 15, 16, 20, 24, 25, 30, 37, 38, 49, 50, 74, 75, 100, 101,
+            ],
+        );
+        Ok(Some(
+            brightnesses
+                .into_iter()
+                .map(|b| calc.sparkball_combinations(b))
+                .inspect(|res| trace!("{:?}", res))
                 .sum(),
-        )
+        ))
     }
 }
 
@@ -181,7 +183,11 @@ mod test {
 7
 16"#;
         let solution = get_solution();
-        assert_eq!(solution.part1(test_input), Some(10));
+        assert_eq!(solution.part1(test_input), Ok(Some(10)));
+        assert_eq!(
+            solution.part1("2\n-"),
+            Err(ParseError::new(2, 1, "expected i64, got `-`"))
+        );
     }
 
     #[test]
@@ -191,7 +197,7 @@ mod test {
 41
 55
 99"#;
-        assert_eq!(solution.part2(test_input), Some(10));
+        assert_eq!(solution.part2(test_input), Ok(Some(10)));
     }
 
     #[test]
@@ -200,6 +206,6 @@ mod test {
         let test_input = r#"156488
 352486
 546212"#;
-        assert_eq!(solution.part3(test_input), Some(10449));
+        assert_eq!(solution.part3(test_input), Ok(Some(10449)));
     }
 }
@@ -1,20 +1,20 @@
-// Template for new days
-use crate::solution::Solution;
+// Template for new days, see `everybody-codes new <day>`. DayX, X and AnswerT get filled in
+use crate::solution::{PartResult, Solution};
 
-pub struct Day5;
+pub struct DayX;
 
-impl Solution<i64> for Day5 {
-    const DAY: usize = 5;
+impl Solution<AnswerT> for DayX {
+    const DAY: usize = X;
 
-    fn part1(&self, input: &str) -> Option<i64> {
+    fn part1(&self, _input: &str) -> PartResult<AnswerT> {
         todo!()
     }
 
-    fn part2(&self, input: &str) -> Option<i64> {
+    fn part2(&self, _input: &str) -> PartResult<AnswerT> {
         todo!()
     }
 
-    fn part3(&self, input: &str) -> Option<i64> {
+    fn part3(&self, _input: &str) -> PartResult<AnswerT> {
         todo!()
     }
 }
@@ -23,28 +23,28 @@ impl Solution<i64> for Day5 {
 mod test {
     use super::*;
 
-    fn get_solution() -> impl Solution<i64> {
-        Day5
+    fn get_solution() -> impl Solution<AnswerT> {
+        DayX
     }
 
     #[test]
     fn test_part1() {
         let test_input = r#""#;
         let solution = get_solution();
-        assert_eq!(solution.part1(test_input), None);
+        assert_eq!(solution.part1(test_input), Ok(None));
     }
 
     #[test]
     fn test_part2() {
         let solution = get_solution();
         let test_input = r#""#;
-        assert_eq!(solution.part2(test_input), None);
+        assert_eq!(solution.part2(test_input), Ok(None));
     }
 
     #[test]
     fn test_part3() {
         let solution = get_solution();
         let test_input = r#""#;
-        assert_eq!(solution.part3(test_input), None);
+        assert_eq!(solution.part3(test_input), Ok(None));
     }
 }
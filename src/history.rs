@@ -0,0 +1,236 @@
+use std::{
+    fmt::Display,
+    fs::OpenOptions,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use crate::{
+    bench::{change_percent, Baseline},
+    runner::{PartReport, PartStatus},
+};
+
+pub const HISTORY_FILE: &str = "ec-history.txt";
+/// Comparing against this name uses the most recent timing of every part in the history instead of a saved baseline
+pub const PREVIOUS: &str = "previous";
+/// Percent a part may get slower before it is flagged
+pub const DEFAULT_THRESHOLD: usize = 10;
+
+/// $CARGO_TARGET_DIR, or target/ in the crate root
+pub fn target_dir() -> PathBuf {
+    std::env::var_os("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join("target"))
+}
+
+pub fn history_path() -> PathBuf {
+    target_dir().join(HISTORY_FILE)
+}
+
+/// Timings of all parts that produced an answer, failed parts say nothing about performance.
+/// Variants are left out, their inputs may be nothing like the regular ones
+pub fn timings(reports: &[PartReport]) -> Baseline {
+    let mut timings = Baseline::default();
+    for report in reports {
+        if let (PartStatus::Answer(_), None) = (&report.status, &report.variant) {
+            timings.insert(report.day, report.part, report.elapsed);
+        }
+    }
+    timings
+}
+
+/// Appends one run to the history, as a `# run <unix seconds>` line followed by the timings.
+/// Runs without any timings are not worth remembering and skipped
+pub fn append(path: &Path, timings: &Baseline) -> io::Result<()> {
+    if timings.is_empty() {
+        return Ok(());
+    }
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let now = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default();
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    write!(file, "# run {}\n{}", now.as_secs(), timings)
+}
+
+/// All runs in the history, oldest first
+pub fn parse(s: &str) -> Option<Vec<Baseline>> {
+    s.split("# run ")
+        .skip(1)
+        .map(|run| Baseline::parse(run.split_once('\n').map_or("", |(_, timings)| timings)))
+        .collect()
+}
+
+/// The most recent timing of every part in the history, whichever run it was in.
+/// None if there is no history yet
+pub fn load_previous(path: &Path) -> io::Result<Option<Baseline>> {
+    let s = match std::fs::read_to_string(path) {
+        Ok(s) => s,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    let runs =
+        parse(&s).ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "malformed history"))?;
+    let mut previous = Baseline::default();
+    for run in &runs {
+        previous.extend(run);
+    }
+    Ok((!previous.is_empty()).then_some(previous))
+}
+
+/// Timings to compare against, chosen with `--compare`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Comparison {
+    pub name: String,
+    pub baseline: Baseline,
+    /// percent a part may get slower before it is flagged
+    pub threshold: usize,
+}
+
+impl Comparison {
+    /// Loads the previous run for [`PREVIOUS`], otherwise the saved baseline of that name
+    pub fn load(name: &str, threshold: usize) -> io::Result<Self> {
+        let baseline = if name == PREVIOUS {
+            load_previous(&history_path())?.ok_or_else(|| {
+                io::Error::new(io::ErrorKind::NotFound, "no previous run in the history")
+            })?
+        } else {
+            Baseline::load(name)?
+        };
+        Ok(Self {
+            name: name.to_string(),
+            baseline,
+            threshold,
+        })
+    }
+
+    pub fn is_regression(&self, old: Duration, new: Duration) -> bool {
+        change_percent(old, new) > self.threshold as f64
+    }
+
+    /// Parts that got slower by more than the threshold. Parts missing on either side are ignored, see [`Self::missing`]
+    pub fn regressions(&self, new: &Baseline) -> Vec<Regression> {
+        new.iter()
+            .filter_map(|(day, part, new)| {
+                let old = self.baseline.get(day, part)?;
+                self.is_regression(old, new).then(|| Regression {
+                    day,
+                    part,
+                    old,
+                    new,
+                    change_percent: change_percent(old, new),
+                })
+            })
+            .collect()
+    }
+
+    /// (day, part) of the new timings the baseline knows nothing about, so they can't be compared
+    pub fn missing(&self, new: &Baseline) -> Vec<(usize, usize)> {
+        new.iter()
+            .filter(|&(day, part, _)| self.baseline.get(day, part).is_none())
+            .map(|(day, part, _)| (day, part))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Regression {
+    pub day: usize,
+    pub part: usize,
+    pub old: Duration,
+    pub new: Duration,
+    pub change_percent: f64,
+}
+
+impl Display for Regression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Day {} Part {}: {:?} -> {:?} ({:+.1}%)",
+            self.day, self.part, self.old, self.new, self.change_percent
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::input::TempDir;
+
+    #[test]
+    fn test_history() {
+        let dir = TempDir::new("history");
+        let path = dir.join(HISTORY_FILE);
+        assert_eq!(load_previous(&path).unwrap(), None);
+
+        let mut first = Baseline::default();
+        first.insert(4, 3, Duration::from_secs(2));
+        first.insert(7, 1, Duration::from_secs(2));
+        append(&path, &first).unwrap();
+        let mut second = Baseline::default();
+        second.insert(4, 3, Duration::from_millis(2100));
+        second.insert(7, 1, Duration::from_secs(3));
+        second.insert(9, 2, Duration::from_secs(3));
+        append(&path, &second).unwrap();
+
+        let s = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(parse(&s), Some(vec![first.clone(), second.clone()]));
+        assert_eq!(parse("# run 1\n07_p1 soon\n"), None);
+        assert_eq!(load_previous(&path).unwrap(), Some(second.clone()));
+        // empty runs are not written at all
+        append(&path, &Baseline::default()).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), s);
+
+        let comparison = Comparison {
+            name: PREVIOUS.to_string(),
+            baseline: first,
+            threshold: DEFAULT_THRESHOLD,
+        };
+        assert_eq!(
+            comparison.regressions(&second),
+            vec![Regression {
+                day: 7,
+                part: 1,
+                old: Duration::from_secs(2),
+                new: Duration::from_secs(3),
+                change_percent: 50.0,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_previous_per_part() {
+        let dir = TempDir::new("history-previous");
+        let path = dir.join(HISTORY_FILE);
+        let mut day4 = Baseline::default();
+        day4.insert(4, 1, Duration::from_secs(1));
+        day4.insert(4, 3, Duration::from_secs(2));
+        append(&path, &day4).unwrap();
+        let mut day7 = Baseline::default();
+        day7.insert(7, 1, Duration::from_secs(3));
+        append(&path, &day7).unwrap();
+        let mut day4_again = Baseline::default();
+        day4_again.insert(4, 3, Duration::from_secs(5));
+        append(&path, &day4_again).unwrap();
+
+        // the last run only covered day 4 part 3, the other parts come from older runs
+        let previous = load_previous(&path).unwrap().unwrap();
+        assert_eq!(previous.get(4, 1), Some(Duration::from_secs(1)));
+        assert_eq!(previous.get(4, 3), Some(Duration::from_secs(5)));
+        assert_eq!(previous.get(7, 1), Some(Duration::from_secs(3)));
+
+        let comparison = Comparison {
+            name: PREVIOUS.to_string(),
+            baseline: previous,
+            threshold: 0,
+        };
+        let mut new = Baseline::default();
+        new.insert(1, 1, Duration::from_secs(1));
+        new.insert(7, 1, Duration::from_secs(3));
+        assert_eq!(comparison.regressions(&new), vec![]);
+        assert_eq!(comparison.missing(&new), vec![(1, 1)]);
+    }
+}
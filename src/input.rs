@@ -0,0 +1,190 @@
+use std::{
+    fmt::Display,
+    io,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+/// Overrides the input directory if no --input-dir is given
+pub const INPUT_DIR_ENV: &str = "EC_INPUT_DIR";
+
+/// Reading from this path reads stdin instead
+pub const STDIN: &str = "-";
+
+/// stdin can only be read once, but benchmarks need the input repeatedly
+static STDIN_INPUT: OnceLock<Result<String, io::ErrorKind>> = OnceLock::new();
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputError {
+    Missing(PathBuf),
+    Unreadable(PathBuf, io::ErrorKind),
+    Empty(PathBuf),
+}
+
+impl InputError {
+    pub fn path(&self) -> &Path {
+        match self {
+            InputError::Missing(path)
+            | InputError::Unreadable(path, _)
+            | InputError::Empty(path) => path,
+        }
+    }
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::Missing(path) => write!(f, "missing file {}", path.display()),
+            InputError::Unreadable(path, kind) => {
+                write!(f, "unreadable file {} ({})", path.display(), kind)
+            }
+            InputError::Empty(path) => write!(f, "empty file {}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+/// Picks the input directory: the command line wins over the environment, which wins over `input/` in the crate root
+pub fn resolve_input_dir(from_cli: Option<PathBuf>) -> PathBuf {
+    from_cli
+        .or_else(|| std::env::var_os(INPUT_DIR_ENV).map(PathBuf::from))
+        .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join("input"))
+}
+
+/// `DD_pP`, followed by `.variant` for variants. Used for the file names and as key in answers.txt
+pub fn input_key(day: usize, part: usize, variant: Option<&str>) -> String {
+    match variant {
+        Some(variant) => format!("{:0>2}_p{}.{}", day, part, variant),
+        None => format!("{:0>2}_p{}", day, part),
+    }
+}
+
+pub fn input_path(input_dir: &Path, day: usize, part: usize) -> PathBuf {
+    variant_path(input_dir, day, part, None)
+}
+
+/// Variants are alternative inputs, like `05_p1.alice.txt` for someone else's account
+pub fn variant_path(input_dir: &Path, day: usize, part: usize, variant: Option<&str>) -> PathBuf {
+    input_dir.join(format!("{}.txt", input_key(day, part, variant)))
+}
+
+/// Names of all variants of a part in the input directory, sorted
+pub fn find_variants(input_dir: &Path, day: usize, part: usize) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(input_dir) else {
+        return Vec::new();
+    };
+    let prefix = format!("{}.", input_key(day, part, None));
+    let mut variants = entries
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            let variant = name.strip_prefix(&prefix)?.strip_suffix(".txt")?;
+            (!variant.is_empty()).then(|| variant.to_string())
+        })
+        .collect::<Vec<_>>();
+    variants.sort();
+    variants
+}
+
+/// Reads the input file, or stdin if the path is [`STDIN`]
+pub fn read_input(path: &Path) -> Result<String, InputError> {
+    let contents = if path == Path::new(STDIN) {
+        STDIN_INPUT
+            .get_or_init(|| io::read_to_string(io::stdin()).map_err(|e| e.kind()))
+            .clone()
+    } else {
+        std::fs::read_to_string(path).map_err(|e| e.kind())
+    };
+    match contents {
+        Ok(input) if input.trim().is_empty() => Err(InputError::Empty(path.to_path_buf())),
+        Ok(input) => Ok(input),
+        Err(io::ErrorKind::NotFound) => Err(InputError::Missing(path.to_path_buf())),
+        Err(kind) => Err(InputError::Unreadable(path.to_path_buf(), kind)),
+    }
+}
+
+/// Scratch directory for tests, removed again when dropped so failing tests don't leave it behind
+#[cfg(test)]
+pub struct TempDir(PathBuf);
+
+#[cfg(test)]
+impl TempDir {
+    /// name has to be unique among the tests, they run in parallel
+    pub fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("ec-{}-test-{}", name, std::process::id()));
+        std::fs::create_dir_all(&path).unwrap();
+        Self(path)
+    }
+}
+
+#[cfg(test)]
+impl std::ops::Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_input_path() {
+        assert_eq!(
+            input_path(Path::new("input"), 7, 3),
+            Path::new("input/07_p3.txt")
+        );
+        assert_eq!(
+            variant_path(Path::new("input"), 5, 1, Some("alice")),
+            Path::new("input/05_p1.alice.txt")
+        );
+        assert_eq!(
+            resolve_input_dir(Some(PathBuf::from("elsewhere"))),
+            Path::new("elsewhere")
+        );
+    }
+
+    #[test]
+    fn test_read_input() {
+        let dir = TempDir::new("input");
+        let empty = dir.join("empty.txt");
+        std::fs::write(&empty, "\n").unwrap();
+        let full = dir.join("full.txt");
+        std::fs::write(&full, "42").unwrap();
+
+        assert_eq!(read_input(&full), Ok("42".to_string()));
+        assert_eq!(read_input(&empty), Err(InputError::Empty(empty.clone())));
+        let missing = dir.join("missing.txt");
+        assert_eq!(read_input(&missing), Err(InputError::Missing(missing)));
+        // reading a directory fails, but not because it is missing
+        assert!(matches!(
+            read_input(&dir),
+            Err(InputError::Unreadable(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_find_variants() {
+        let dir = TempDir::new("variants");
+        for name in [
+            "05_p1.txt",
+            "05_p1.bob.txt",
+            "05_p1.alice.txt",
+            "05_p2.carol.txt",
+            "15_p1.dave.txt",
+        ] {
+            std::fs::write(dir.join(name), "1").unwrap();
+        }
+        assert_eq!(find_variants(&dir, 5, 1), vec!["alice", "bob"]);
+        assert_eq!(find_variants(&dir, 5, 3), Vec::<String>::new());
+    }
+}
@@ -0,0 +1,103 @@
+use std::{
+    fmt::{self, Display},
+    fs::File,
+    io::{self, Write},
+    path::Path,
+    sync::{
+        atomic::{AtomicU8, Ordering},
+        Mutex,
+    },
+};
+
+/// How chatty the diagnostics are, each level includes the ones before it
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Off,
+    #[default]
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    const ALL: [Level; 4] = [Level::Off, Level::Info, Level::Debug, Level::Trace];
+
+    /// one step more verbose, saturating at trace
+    pub fn more(self) -> Self {
+        Self::ALL[(self as usize + 1).min(Self::ALL.len() - 1)]
+    }
+}
+
+impl Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Level::Off => "off",
+            Level::Info => "info",
+            Level::Debug => "debug",
+            Level::Trace => "trace",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Info as u8);
+/// None logs to stderr
+static LOG_FILE: Mutex<Option<File>> = Mutex::new(None);
+
+/// Sets the level and where messages go, stderr if no file is given
+pub fn init(level: Level, file: Option<&Path>) -> io::Result<()> {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+    let file = file.map(File::create).transpose()?;
+    *LOG_FILE.lock().unwrap_or_else(|e| e.into_inner()) = file;
+    Ok(())
+}
+
+pub fn enabled(level: Level) -> bool {
+    level != Level::Off && level as u8 <= LEVEL.load(Ordering::Relaxed)
+}
+
+/// Use the macros instead, they skip formatting when the level is disabled
+pub fn write(level: Level, args: fmt::Arguments) {
+    let line = format!("[{}] {}\n", level, args);
+    // a poisoned lock only means another thread panicked while logging, the file is still fine
+    let mut file = LOG_FILE.lock().unwrap_or_else(|e| e.into_inner());
+    let _ = match file.as_mut() {
+        Some(file) => file.write_all(line.as_bytes()),
+        None => io::stderr().write_all(line.as_bytes()),
+    };
+}
+
+macro_rules! log {
+    ($level:expr, $($arg:tt)+) => {
+        if $crate::log::enabled($level) {
+            $crate::log::write($level, format_args!($($arg)+));
+        }
+    };
+}
+
+macro_rules! info {
+    ($($arg:tt)+) => { $crate::log::log!($crate::log::Level::Info, $($arg)+) };
+}
+
+macro_rules! debug {
+    ($($arg:tt)+) => { $crate::log::log!($crate::log::Level::Debug, $($arg)+) };
+}
+
+macro_rules! trace {
+    ($($arg:tt)+) => { $crate::log::log!($crate::log::Level::Trace, $($arg)+) };
+}
+
+pub(crate) use {debug, info, log, trace};
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_levels() {
+        assert_eq!(Level::Off.more(), Level::Info);
+        assert_eq!(Level::Info.more().more(), Level::Trace);
+        assert_eq!(Level::Trace.more(), Level::Trace);
+        assert!(Level::Debug > Level::Info);
+    }
+}
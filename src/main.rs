@@ -1,8 +1,17 @@
-use std::time::Instant;
+use std::{path::Path, process::ExitCode, thread, time::Instant};
 
-use solution::Solution;
-/*
+use answers::{KnownAnswers, Verdict};
+use bench::BenchOptions;
+use cli::{Args, CliError, DaySelection, Mode};
+use history::Comparison;
+use runner::{format::OutputFormat, RunConfig};
+
+mod alloc_stats;
+mod answers;
+mod bench;
+mod cli;
 mod day1;
+mod day10;
 mod day2;
 mod day3;
 mod day4;
@@ -11,49 +20,248 @@ mod day6;
 mod day7;
 mod day8;
 mod day9;
-*/
-mod day10;
+mod history;
+mod input;
+mod log;
+mod registry;
+mod runner;
+mod scaffold;
 pub mod solution;
 mod utils;
+mod watch;
 
-fn get_input(day: usize, part: usize) -> Option<String> {
-    let path = format!("input/{:0>2}_p{}.txt", day, part);
-    std::fs::read_to_string(path).ok()
+/// Prints extra information after the reports. Machine readable formats keep stdout parseable,
+/// so it goes to stderr there, without the blank separator lines
+fn print_note(format: OutputFormat, line: &str) {
+    match format {
+        OutputFormat::Text => println!("{}", line),
+        _ if !line.is_empty() => eprintln!("{}", line),
+        _ => {}
+    }
 }
-fn run_part<T: std::fmt::Debug>(solution: &impl Solution<T>, part: usize) {
-    let now = Instant::now();
-    let day = solution.get_day();
-    let input = get_input(day, part);
-    if let Some(input) = input {
-        let result = match part {
-            1 => solution.part1(&input),
-            2 => solution.part2(&input),
-            3 => solution.part3(&input),
-            _ => panic!("Invalid part"),
+
+fn main() -> ExitCode {
+    let args = match Args::parse(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(CliError::Help) => {
+            println!("{}", CliError::Help);
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    if let Err(e) = log::init(args.log_level, args.log_file.as_deref()) {
+        eprintln!("Could not open log file: {}", e);
+        return ExitCode::FAILURE;
+    }
+    let parts = match args.part {
+        Some(part) => vec![part],
+        None => runner::ALL_PARTS.to_vec(),
+    };
+    let jobs = match args.jobs {
+        None => 1,
+        Some(0) => thread::available_parallelism().map_or(1, |n| n.get()),
+        Some(jobs) => jobs,
+    };
+    if args.mode != Mode::Bench && args.bench != BenchOptions::default() {
+        eprintln!("--iterations, --warmup and --save only work with bench");
+        return ExitCode::FAILURE;
+    }
+    if args.mode == Mode::Bench && (args.watch || args.format != OutputFormat::Text) {
+        eprintln!("bench can't be combined with --watch or --format");
+        return ExitCode::FAILURE;
+    }
+    if args.watch && args.input.as_deref() == Some(Path::new(input::STDIN)) {
+        eprintln!("--watch can't watch stdin");
+        return ExitCode::FAILURE;
+    }
+    // watch never returns, so it never gets to the history, comparison and answers after the run
+    if args.watch && (args.verify || args.record || args.compare.is_some()) {
+        eprintln!("--watch can't be combined with --verify, --record or --compare");
+        return ExitCode::FAILURE;
+    }
+    if args.input.is_some() {
+        if args.days == DaySelection::All || args.part.is_none() {
+            eprintln!("--input needs a single day and --part");
+            return ExitCode::FAILURE;
+        }
+        // the known answers belong to the regular inputs
+        if args.verify || args.record {
+            eprintln!("--input can't be combined with --verify or --record");
+            return ExitCode::FAILURE;
+        }
+    }
+    let config = RunConfig {
+        input_dir: input::resolve_input_dir(args.input_dir),
+        input_file: args.input,
+        jobs,
+        format: args.format,
+        timeout: args.timeout,
+    };
+    if args.mode == Mode::New {
+        let DaySelection::Day(day) = args.days else {
+            eprintln!("new needs a day");
+            return ExitCode::FAILURE;
+        };
+        let answer_type = args.answer_type.as_deref().unwrap_or("i64");
+        let src_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+        return match scaffold::scaffold(day, answer_type, &src_dir, &config.input_dir) {
+            Ok(changed) => {
+                for path in changed {
+                    println!("Wrote {}", path.display());
+                }
+                ExitCode::SUCCESS
+            }
+            Err(e) => {
+                eprintln!("Could not create day {}: {}", day, e);
+                ExitCode::FAILURE
+            }
+        };
+    }
+
+    // load before running, so `previous` doesn't refer to this run
+    let comparison = match &args.compare {
+        Some(name) => {
+            let threshold = args.threshold.unwrap_or(history::DEFAULT_THRESHOLD);
+            match Comparison::load(name, threshold) {
+                Ok(comparison) => Some(comparison),
+                Err(e) => {
+                    eprintln!("Could not load {}: {}", name, e);
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+        None => None,
+    };
+
+    if args.mode == Mode::Bench {
+        let solution = match args.days {
+            DaySelection::Latest => registry::latest(),
+            DaySelection::Day(day) => match registry::get(day) {
+                Some(solution) => solution,
+                None => {
+                    eprintln!("Day {} is not implemented", day);
+                    return ExitCode::FAILURE;
+                }
+            },
+            DaySelection::All => {
+                eprintln!("bench needs a single day");
+                return ExitCode::FAILURE;
+            }
         };
-        if let Some(result) = result {
+        if let Err(e) =
+            bench::bench_day(solution, &parts, &config, &args.bench, comparison.as_ref())
+        {
+            eprintln!("Benchmark failed: {}", e);
+            return ExitCode::FAILURE;
+        }
+        return ExitCode::SUCCESS;
+    }
+
+    let solutions = match args.days {
+        DaySelection::All => registry::SOLUTIONS.to_vec(),
+        DaySelection::Latest => vec![registry::latest()],
+        DaySelection::Day(day) => match registry::get(day) {
+            Some(solution) => vec![solution],
+            None => {
+                eprintln!("Day {} is not implemented", day);
+                return ExitCode::FAILURE;
+            }
+        },
+    };
+    let run = || {
+        let now = Instant::now();
+        let reports = runner::run(&solutions, &parts, &config);
+        if args.days == DaySelection::All && args.format == OutputFormat::Text {
+            println!();
             println!(
-                "Day {} Part {}: {:?} - elapsed: {:?}",
-                day,
-                part,
-                result,
-                now.elapsed()
+                "{}",
+                runner::summary::summary_table(&reports, now.elapsed())
             );
-        } else {
-            println!("Day {} Part {}: No solution", day, part);
         }
-    } else {
-        println!("Day {} Part {}: No input", day, part);
+        reports
+    };
+    if args.watch {
+        let paths = watch::watched_files(&solutions, &parts, &config);
+        watch::watch(&paths, || {
+            run();
+        });
     }
-}
+    let reports = run();
 
-fn run_day<T: std::fmt::Debug>(solution: impl Solution<T>) {
-    run_part(&solution, 1);
-    run_part(&solution, 2);
-    run_part(&solution, 3);
-}
+    // timings of other inputs would only confuse the comparisons
+    let timings = history::timings(&reports);
+    if config.input_file.is_none() {
+        if let Err(e) = history::append(&history::history_path(), &timings) {
+            eprintln!("Could not update the timing history: {}", e);
+        }
+    }
+    if let Some(comparison) = &comparison {
+        let regressions = comparison.regressions(&timings);
+        let missing = comparison.missing(&timings);
+        print_note(args.format, "");
+        if timings.is_empty() {
+            print_note(
+                args.format,
+                &format!(
+                    "No part produced an answer to compare with {}",
+                    comparison.name
+                ),
+            );
+        } else if regressions.is_empty() && missing.is_empty() {
+            print_note(
+                args.format,
+                &format!(
+                    "No part is more than {}% slower than {}",
+                    comparison.threshold, comparison.name
+                ),
+            );
+        }
+        for regression in regressions {
+            print_note(args.format, &format!("REGRESSION {}", regression));
+        }
+        for (day, part) in missing {
+            print_note(
+                args.format,
+                &format!(
+                    "Day {} Part {}: not in {}, nothing to compare",
+                    day, part, comparison.name
+                ),
+            );
+        }
+    }
 
-fn main() {
-    let today = day10::Day10;
-    run_day(today);
+    if !args.verify && !args.record {
+        return ExitCode::SUCCESS;
+    }
+    let answers_path = answers::answers_path(&config.input_dir);
+    let mut known_answers = match KnownAnswers::load(&answers_path) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("Could not read {}: {}", answers_path.display(), e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut exit_code = ExitCode::SUCCESS;
+    if args.verify {
+        print_note(args.format, "");
+        for report in &reports {
+            let verdict = known_answers.verify(report);
+            if matches!(verdict, Verdict::Fail { .. }) {
+                exit_code = ExitCode::FAILURE;
+            }
+            print_note(args.format, &format!("{}: {}", report.name(), verdict));
+        }
+    }
+    if args.record {
+        known_answers.record(&reports);
+        if let Err(e) = known_answers.save(&answers_path) {
+            eprintln!("Could not write {}: {}", answers_path.display(), e);
+            return ExitCode::FAILURE;
+        }
+        eprintln!("Recorded answers in {}", answers_path.display());
+    }
+    exit_code
 }
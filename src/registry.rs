@@ -0,0 +1,44 @@
+use crate::solution::{DynSolution, ErasedSolution};
+
+/// All implemented days, sorted by day
+pub static SOLUTIONS: &[&dyn DynSolution] = &[
+    &ErasedSolution::new(crate::day1::Day1),
+    &ErasedSolution::new(crate::day2::Day2),
+    &ErasedSolution::new(crate::day3::Day3),
+    &ErasedSolution::new(crate::day4::Day4),
+    &ErasedSolution::new(crate::day5::Day5),
+    &ErasedSolution::new(crate::day6::Day6),
+    &ErasedSolution::new(crate::day7::Day7),
+    &ErasedSolution::new(crate::day8::Day8),
+    &ErasedSolution::new(crate::day9::Day9),
+    &ErasedSolution::new(crate::day10::Day10),
+];
+
+pub fn get(day: usize) -> Option<&'static dyn DynSolution> {
+    SOLUTIONS.iter().copied().find(|s| s.day() == day)
+}
+
+pub fn latest() -> &'static dyn DynSolution {
+    *SOLUTIONS.last().expect("no days registered")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_sorted_and_unique() {
+        let days = SOLUTIONS.iter().map(|s| s.day()).collect::<Vec<_>>();
+        assert!(days.windows(2).all(|w| w[0] < w[1]), "{:?}", days);
+    }
+
+    #[test]
+    fn test_get() {
+        assert_eq!(get(7).map(|s| s.day()), Some(7));
+        assert!(get(0).is_none());
+        assert_eq!(
+            Some(latest().day()),
+            SOLUTIONS.iter().map(|s| s.day()).max()
+        );
+    }
+}
@@ -0,0 +1,192 @@
+use std::{fmt::Write, str::FromStr};
+
+use super::{PartReport, PartStatus};
+
+/// How reports are written to stdout
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    /// one JSON object per line
+    Json,
+    Csv,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            "csv" => Ok(OutputFormat::Csv),
+            _ => Err(format!("unknown output format: {}", s)),
+        }
+    }
+}
+
+const FIELDS: [&str; 12] = [
+    "day",
+    "part",
+    "variant",
+    "status",
+    "answer",
+    "elapsed_ns",
+    "parse_ns",
+    "solve_ns",
+    "alloc_count",
+    "alloc_bytes",
+    "alloc_peak",
+    "input",
+];
+
+pub const CSV_HEADER: &str =
+    "day,part,variant,status,answer,elapsed_ns,parse_ns,solve_ns,alloc_count,alloc_bytes,alloc_peak,input";
+
+fn answer(report: &PartReport) -> Option<String> {
+    match &report.status {
+        PartStatus::Answer(answer) => Some(answer.to_string()),
+        _ => None,
+    }
+}
+
+/// count, bytes and peak, None without the `alloc-stats` feature
+fn alloc_fields(report: &PartReport) -> [Option<String>; 3] {
+    match report.allocs {
+        Some(allocs) => [allocs.count, allocs.bytes, allocs.peak].map(|n| Some(n.to_string())),
+        None => [None, None, None],
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => write!(escaped, "\\u{:04x}", c as u32).unwrap(),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+/// Quotes the field if it would otherwise break the row
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/// A single JSON Lines record, missing variants, answers, parse times and allocations are null
+pub fn json_record(report: &PartReport) -> String {
+    let [count, bytes, peak] = alloc_fields(report).map(|n| n.unwrap_or("null".to_string()));
+    let values = [
+        report.day.to_string(),
+        report.part.to_string(),
+        report
+            .variant
+            .as_deref()
+            .map_or("null".to_string(), json_string),
+        json_string(report.status.label()),
+        answer(report).map_or("null".to_string(), |a| json_string(&a)),
+        report.elapsed.as_nanos().to_string(),
+        report
+            .parse_time
+            .map_or("null".to_string(), |parse| parse.as_nanos().to_string()),
+        report.solve_time().as_nanos().to_string(),
+        count,
+        bytes,
+        peak,
+        json_string(&report.input.to_string_lossy()),
+    ];
+    let fields = FIELDS
+        .iter()
+        .zip(values)
+        .map(|(field, value)| format!("\"{}\":{}", field, value))
+        .collect::<Vec<_>>();
+    format!("{{{}}}", fields.join(","))
+}
+
+/// A single CSV row matching [`CSV_HEADER`], missing variants, answers, parse times and allocations are empty
+pub fn csv_record(report: &PartReport) -> String {
+    let [count, bytes, peak] = alloc_fields(report).map(Option::unwrap_or_default);
+    [
+        report.day.to_string(),
+        report.part.to_string(),
+        report.variant.clone().unwrap_or_default(),
+        report.status.label().to_string(),
+        answer(report).unwrap_or_default(),
+        report.elapsed.as_nanos().to_string(),
+        report
+            .parse_time
+            .map_or(String::new(), |parse| parse.as_nanos().to_string()),
+        report.solve_time().as_nanos().to_string(),
+        count,
+        bytes,
+        peak,
+        report.input.to_string_lossy().into_owned(),
+    ]
+    .map(|field| csv_field(&field))
+    .join(",")
+}
+
+#[cfg(test)]
+mod test {
+    use std::{path::PathBuf, time::Duration};
+
+    use super::*;
+    use crate::alloc_stats::AllocStats;
+
+    #[test]
+    fn test_records() {
+        let answer = PartReport {
+            day: 7,
+            part: 2,
+            variant: Some("alice".to_string()),
+            status: PartStatus::Answer("A,\"B\"".into()),
+            elapsed: Duration::from_micros(15),
+            parse_time: Some(Duration::from_micros(5)),
+            allocs: Some(AllocStats {
+                count: 2,
+                bytes: 64,
+                peak: 48,
+            }),
+            input: PathBuf::from("in/07_p2.txt"),
+        };
+        assert_eq!(
+            json_record(&answer),
+            r#"{"day":7,"part":2,"variant":"alice","status":"answer","answer":"A,\"B\"","elapsed_ns":15000,"parse_ns":5000,"solve_ns":10000,"alloc_count":2,"alloc_bytes":64,"alloc_peak":48,"input":"in/07_p2.txt"}"#
+        );
+        assert_eq!(
+            csv_record(&answer),
+            r#"7,2,alice,answer,"A,""B""",15000,5000,10000,2,64,48,in/07_p2.txt"#
+        );
+
+        let missing = PartReport {
+            status: PartStatus::NoSolution,
+            elapsed: Duration::ZERO,
+            variant: None,
+            parse_time: None,
+            allocs: None,
+            ..answer
+        };
+        assert_eq!(
+            json_record(&missing),
+            r#"{"day":7,"part":2,"variant":null,"status":"no solution","answer":null,"elapsed_ns":0,"parse_ns":null,"solve_ns":0,"alloc_count":null,"alloc_bytes":null,"alloc_peak":null,"input":"in/07_p2.txt"}"#
+        );
+        assert_eq!(
+            csv_record(&missing),
+            "7,2,,no solution,,0,,0,,,,in/07_p2.txt"
+        );
+        assert_eq!("csv".parse(), Ok(OutputFormat::Csv));
+        assert!("xml".parse::<OutputFormat>().is_err());
+    }
+}
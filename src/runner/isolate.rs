@@ -0,0 +1,77 @@
+use std::{
+    cell::{Cell, RefCell},
+    fmt::Display,
+    panic::{self, AssertUnwindSafe},
+    sync::Once,
+};
+
+/// What we know about a panic inside an isolated part
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PanicDetails {
+    pub message: String,
+    /// file:line:column
+    pub location: Option<String>,
+}
+
+impl Display for PanicDetails {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.location {
+            Some(location) => write!(f, "{} at {}", self.message, location),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+thread_local! {
+    static ISOLATING: Cell<bool> = const { Cell::new(false) };
+    static LAST_PANIC: RefCell<Option<PanicDetails>> = const { RefCell::new(None) };
+}
+
+static INSTALL_HOOK: Once = Once::new();
+
+/// Our hook records panics of isolated code instead of printing them, everything else goes to the default hook
+fn install_hook() {
+    INSTALL_HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !ISOLATING.get() {
+                default_hook(info);
+                return;
+            }
+            let details = PanicDetails {
+                message: info.payload_as_str().unwrap_or("unknown panic").to_string(),
+                location: info.location().map(|l| l.to_string()),
+            };
+            LAST_PANIC.set(Some(details));
+        }));
+    });
+}
+
+/// Runs f, turning a panic into an error instead of unwinding further
+pub fn isolate<T>(f: impl FnOnce() -> T) -> Result<T, PanicDetails> {
+    install_hook();
+    let was_isolating = ISOLATING.replace(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    ISOLATING.set(was_isolating);
+    result.map_err(|_| {
+        LAST_PANIC.take().unwrap_or_else(|| PanicDetails {
+            message: "unknown panic".to_string(),
+            location: None,
+        })
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_isolate() {
+        assert_eq!(isolate(|| 42), Ok(42));
+        let line = line!() + 1;
+        let details = isolate(|| -> i32 { todo!() }).unwrap_err();
+        assert_eq!(details.message, "not yet implemented");
+        let location = details.location.unwrap();
+        assert!(location.starts_with(&format!("{}:{}:", file!(), line)));
+    }
+}
@@ -0,0 +1,437 @@
+use std::{
+    fmt::Display,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    alloc_stats::{self, AllocStats},
+    input::{self, InputError},
+    log::info,
+    solution::{Answer, DynSolution, SolutionError, TimedRun},
+};
+
+pub mod format;
+pub mod isolate;
+pub mod summary;
+
+use format::OutputFormat;
+use isolate::PanicDetails;
+
+pub const ALL_PARTS: [usize; 3] = [1, 2, 3];
+
+/// Settings shared by all parts of a run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunConfig {
+    pub input_dir: PathBuf,
+    /// read instead of the file in input_dir, only makes sense when running a single part
+    pub input_file: Option<PathBuf>,
+    /// number of worker threads, 1 runs everything on the current thread
+    pub jobs: usize,
+    pub format: OutputFormat,
+    /// parts running longer than this are reported as timed out
+    pub timeout: Option<Duration>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PartStatus {
+    Answer(Answer),
+    NoSolution,
+    NoInput(InputError),
+    Error(SolutionError),
+    Panicked(PanicDetails),
+    /// the part was still running when the time limit was reached
+    TimedOut(Duration),
+}
+
+impl PartStatus {
+    /// short name for tables
+    pub fn label(&self) -> &'static str {
+        match self {
+            PartStatus::Answer(_) => "answer",
+            PartStatus::NoSolution => "no solution",
+            PartStatus::NoInput(_) => "no input",
+            PartStatus::Error(_) => "error",
+            PartStatus::Panicked(_) => "panicked",
+            PartStatus::TimedOut(_) => "timed out",
+        }
+    }
+}
+
+/// Result of running a single part
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartReport {
+    pub day: usize,
+    pub part: usize,
+    /// None for the regular input, see [`input::variant_path`]
+    pub variant: Option<String>,
+    pub status: PartStatus,
+    /// time spent in the solution itself, without reading the input
+    pub elapsed: Duration,
+    /// part of elapsed spent parsing, None if the solution has no separate parse step
+    pub parse_time: Option<Duration>,
+    /// None unless built with the `alloc-stats` feature
+    pub allocs: Option<AllocStats>,
+    pub input: PathBuf,
+}
+
+impl PartReport {
+    /// `Day D Part P`, followed by the variant in parentheses
+    pub fn name(&self) -> String {
+        match &self.variant {
+            Some(variant) => format!("Day {} Part {} ({})", self.day, self.part, variant),
+            None => format!("Day {} Part {}", self.day, self.part),
+        }
+    }
+
+    /// elapsed time without parsing
+    pub fn solve_time(&self) -> Duration {
+        self.elapsed
+            .saturating_sub(self.parse_time.unwrap_or_default())
+    }
+}
+
+impl Display for PartReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: ", self.name())?;
+        match &self.status {
+            PartStatus::Answer(answer) => {
+                write!(f, "{} - elapsed: {:?}", answer, self.elapsed)?;
+                match self.parse_time {
+                    Some(parse) => {
+                        write!(f, " (parse: {:?}, solve: {:?})", parse, self.solve_time())
+                    }
+                    None => Ok(()),
+                }?;
+                match self.allocs {
+                    Some(allocs) => write!(f, " - {}", allocs),
+                    None => Ok(()),
+                }
+            }
+            PartStatus::NoSolution => write!(f, "No solution"),
+            PartStatus::NoInput(e) => write!(f, "No input ({})", e),
+            PartStatus::Error(e) => write!(f, "Error at {}", e.error),
+            PartStatus::Panicked(details) => write!(f, "Panicked: {}", details),
+            PartStatus::TimedOut(limit) => write!(f, "Timed out after {:?}", limit),
+        }
+    }
+}
+
+/// One part to run, on the regular input or a variant
+#[derive(Clone)]
+pub struct PlannedRun {
+    pub solution: &'static dyn DynSolution,
+    pub part: usize,
+    pub variant: Option<String>,
+}
+
+impl PlannedRun {
+    pub fn new(solution: &'static dyn DynSolution, part: usize) -> Self {
+        Self {
+            solution,
+            part,
+            variant: None,
+        }
+    }
+
+    pub fn input_path(&self, config: &RunConfig) -> PathBuf {
+        match &config.input_file {
+            Some(path) => path.clone(),
+            None => input::variant_path(
+                &config.input_dir,
+                self.solution.day(),
+                self.part,
+                self.variant.as_deref(),
+            ),
+        }
+    }
+}
+
+/// The regular input of every part, followed by all its variants. An explicit input file has no variants
+pub fn plan(
+    solutions: &[&'static dyn DynSolution],
+    parts: &[usize],
+    config: &RunConfig,
+) -> Vec<PlannedRun> {
+    let mut runs = Vec::new();
+    for &solution in solutions {
+        for &part in parts {
+            runs.push(PlannedRun::new(solution, part));
+            if config.input_file.is_none() {
+                let variants = input::find_variants(&config.input_dir, solution.day(), part);
+                runs.extend(variants.into_iter().map(|variant| PlannedRun {
+                    variant: Some(variant),
+                    ..PlannedRun::new(solution, part)
+                }));
+            }
+        }
+    }
+    runs
+}
+
+/// Runs a single part. Panics are caught and reported, so they don't take down the other parts
+pub fn run_part(run: &PlannedRun, config: &RunConfig) -> PartReport {
+    let PlannedRun {
+        solution,
+        part,
+        ref variant,
+    } = *run;
+    let day = solution.day();
+    let path = run.input_path(config);
+    let input = match input::read_input(&path) {
+        Ok(input) => input,
+        Err(e) => {
+            return PartReport {
+                day,
+                part,
+                variant: variant.clone(),
+                status: PartStatus::NoInput(e),
+                elapsed: Duration::ZERO,
+                parse_time: None,
+                allocs: None,
+                input: path,
+            }
+        }
+    };
+    let timed_run = move || {
+        let now = Instant::now();
+        let (result, allocs) =
+            alloc_stats::measure(|| isolate::isolate(|| solution.run_part_timed(part, &input)));
+        (result, now.elapsed(), allocs)
+    };
+    let (result, elapsed, allocs) = match config.timeout {
+        None => timed_run(),
+        Some(limit) => {
+            // there is no way to stop a thread, so a part that times out keeps running in the
+            // background until it finishes or the process exits
+            let (sender, receiver) = mpsc::channel();
+            thread::spawn(move || {
+                // fails if we stopped waiting already
+                let _ = sender.send(timed_run());
+            });
+            match receiver.recv_timeout(limit) {
+                Ok(finished) => finished,
+                Err(_) => {
+                    info!("Day {} Part {} keeps running in the background", day, part);
+                    return PartReport {
+                        day,
+                        part,
+                        variant: variant.clone(),
+                        status: PartStatus::TimedOut(limit),
+                        elapsed: limit,
+                        parse_time: None,
+                        allocs: None,
+                        input: path,
+                    };
+                }
+            }
+        }
+    };
+    let (status, parse_time) = match result {
+        Ok(TimedRun { result, parse_time }) => {
+            let status = match result {
+                Ok(Some(answer)) => PartStatus::Answer(answer),
+                Ok(None) => PartStatus::NoSolution,
+                Err(e) => PartStatus::Error(e),
+            };
+            (status, parse_time)
+        }
+        Err(details) => (PartStatus::Panicked(details), None),
+    };
+    PartReport {
+        day,
+        part,
+        variant: variant.clone(),
+        status,
+        elapsed,
+        parse_time,
+        allocs,
+        input: path,
+    }
+}
+
+/// Solution errors, and panics in the machine readable formats, are additionally explained on stderr
+fn print_report(report: &PartReport, format: OutputFormat) {
+    match format {
+        OutputFormat::Text => println!("{}", report),
+        OutputFormat::Json => println!("{}", format::json_record(report)),
+        OutputFormat::Csv => println!("{}", format::csv_record(report)),
+    }
+    match &report.status {
+        PartStatus::Error(e) => eprintln!("{}", e.diagnostic()),
+        // the records have no room for the panic message
+        PartStatus::Panicked(_) if format != OutputFormat::Text => eprintln!("{}", report),
+        _ => {}
+    }
+}
+
+/// Runs the given parts of all solutions, including variants, on [`RunConfig::jobs`] threads.
+/// Reports are printed and returned in day/part order, no matter which part finishes first
+pub fn run(
+    solutions: &[&'static dyn DynSolution],
+    parts: &[usize],
+    config: &RunConfig,
+) -> Vec<PartReport> {
+    let jobs = plan(solutions, parts, config);
+    if config.format == OutputFormat::Csv {
+        println!("{}", format::CSV_HEADER);
+    }
+    if config.jobs <= 1 {
+        return jobs
+            .into_iter()
+            .map(|run| {
+                let report = run_part(&run, config);
+                print_report(&report, config.format);
+                report
+            })
+            .collect();
+    }
+
+    let next_job = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..config.jobs.min(jobs.len()) {
+            let sender = sender.clone();
+            let (jobs, next_job) = (&jobs, &next_job);
+            scope.spawn(move || loop {
+                let idx = next_job.fetch_add(1, Ordering::Relaxed);
+                let Some(run) = jobs.get(idx) else {
+                    break;
+                };
+                let report = run_part(run, config);
+                if sender.send((idx, report)).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+
+        // buffer finished parts until everything before them is done
+        let mut finished = vec![None; jobs.len()];
+        let mut printed = 0;
+        for (idx, report) in receiver {
+            finished[idx] = Some(report);
+            while let Some(Some(report)) = finished.get(printed) {
+                print_report(report, config.format);
+                printed += 1;
+            }
+        }
+        finished.into_iter().flatten().collect()
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::input::TempDir;
+
+    /// inputs for all parts of the day, in a directory that lives as long as the returned TempDir
+    fn test_config(name: &str, day: usize) -> (TempDir, RunConfig) {
+        let input_dir = TempDir::new(name);
+        for part in ALL_PARTS {
+            std::fs::write(input::input_path(&input_dir, day, part), "input").unwrap();
+        }
+        let config = RunConfig {
+            input_dir: input_dir.to_path_buf(),
+            input_file: None,
+            jobs: 1,
+            format: OutputFormat::Text,
+            timeout: None,
+        };
+        (input_dir, config)
+    }
+
+    /// answers with the part number, earlier parts take longer
+    struct SlowSolution;
+
+    impl DynSolution for SlowSolution {
+        fn day(&self) -> usize {
+            1
+        }
+
+        fn run_part_timed(&self, part: usize, _input: &str) -> TimedRun {
+            thread::sleep(Duration::from_millis(30 * (3 - part as u64)));
+            TimedRun {
+                result: Ok(Some(Answer::from(part))),
+                parse_time: None,
+            }
+        }
+    }
+
+    #[test]
+    fn test_parallel_order() {
+        let (input_dir, config) = test_config("runner", 1);
+        std::fs::write(
+            input::variant_path(&input_dir, 1, 2, Some("alice")),
+            "input",
+        )
+        .unwrap();
+        let config = RunConfig { jobs: 3, ..config };
+        let reports = run(&[&SlowSolution], &ALL_PARTS, &config);
+        let answers = reports
+            .into_iter()
+            .map(|r| (r.part, r.variant, r.status))
+            .collect::<Vec<_>>();
+        let answer = |part: usize, variant: Option<&str>| {
+            (
+                part,
+                variant.map(str::to_string),
+                PartStatus::Answer(part.into()),
+            )
+        };
+        assert_eq!(
+            answers,
+            [
+                answer(1, None),
+                answer(2, None),
+                answer(2, Some("alice")),
+                answer(3, None)
+            ]
+        );
+    }
+
+    const LIMIT: Duration = Duration::from_millis(50);
+
+    /// part 2 takes far longer than LIMIT, but still ends so no thread outlives the test for long
+    struct HangingSolution;
+
+    impl DynSolution for HangingSolution {
+        fn day(&self) -> usize {
+            2
+        }
+
+        fn run_part_timed(&self, part: usize, _input: &str) -> TimedRun {
+            if part == 2 {
+                thread::sleep(LIMIT * 20);
+            }
+            TimedRun {
+                result: Ok(Some(Answer::from(part))),
+                parse_time: None,
+            }
+        }
+    }
+
+    #[test]
+    fn test_timeout() {
+        let (_input_dir, config) = test_config("timeout", 2);
+        let config = RunConfig {
+            timeout: Some(LIMIT),
+            ..config
+        };
+        let statuses = ALL_PARTS
+            .map(|part| run_part(&PlannedRun::new(&HangingSolution, part), &config).status);
+        assert_eq!(
+            statuses,
+            [
+                PartStatus::Answer(Answer::from(1usize)),
+                PartStatus::TimedOut(LIMIT),
+                PartStatus::Answer(Answer::from(3usize)),
+            ]
+        );
+    }
+}
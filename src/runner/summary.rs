@@ -0,0 +1,132 @@
+use std::{fmt::Write, time::Duration};
+
+use super::{PartReport, PartStatus};
+
+const HEADER: [&str; 6] = ["Day", "Part", "Status", "Answer", "Parse", "Elapsed"];
+/// only shown when there are variants, after the part
+const VARIANT_HEADER: &str = "Variant";
+/// only shown when allocations were counted
+const ALLOCS_HEADER: &str = "Allocations";
+
+/// Renders an aligned table of all reports, followed by the total runtime
+pub fn summary_table(reports: &[PartReport], total: Duration) -> String {
+    let with_variants = reports.iter().any(|report| report.variant.is_some());
+    let with_allocs = reports.iter().any(|report| report.allocs.is_some());
+    let mut header = HEADER.map(str::to_string).to_vec();
+    if with_variants {
+        header.insert(2, VARIANT_HEADER.to_string());
+    }
+    if with_allocs {
+        header.push(ALLOCS_HEADER.to_string());
+    }
+    let rows = reports
+        .iter()
+        .map(|report| {
+            let answer = match &report.status {
+                PartStatus::Answer(answer) => answer.to_string(),
+                PartStatus::Panicked(details) => details.to_string(),
+                PartStatus::Error(e) => e.error.to_string(),
+                PartStatus::NoInput(e) => e.path().display().to_string(),
+                PartStatus::NoSolution | PartStatus::TimedOut(_) => String::new(),
+            };
+            let elapsed = match report.status {
+                PartStatus::NoInput(_) => String::new(),
+                _ => format!("{:?}", report.elapsed),
+            };
+            let parse = report
+                .parse_time
+                .map_or(String::new(), |parse| format!("{:?}", parse));
+            let mut row = vec![
+                report.day.to_string(),
+                report.part.to_string(),
+                report.status.label().to_string(),
+                answer,
+                parse,
+                elapsed,
+            ];
+            if with_variants {
+                row.insert(2, report.variant.clone().unwrap_or_default());
+            }
+            if with_allocs {
+                row.push(report.allocs.map_or(String::new(), |a| a.to_string()));
+            }
+            row
+        })
+        .collect::<Vec<_>>();
+
+    let mut widths = header.iter().map(String::len).collect::<Vec<_>>();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut table = String::new();
+    let mut push_row = |cells: &[String]| {
+        let line = cells
+            .iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(i, (cell, width))| {
+                // numbers are right aligned, everything else left aligned
+                if i < 2 {
+                    format!("{:>width$}", cell, width = width)
+                } else {
+                    format!("{:<width$}", cell, width = width)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("  ");
+        writeln!(table, "{}", line.trim_end()).unwrap();
+    };
+    push_row(&header);
+    push_row(&widths.iter().map(|w| "-".repeat(*w)).collect::<Vec<_>>());
+    for row in &rows {
+        push_row(row);
+    }
+    write!(table, "Total: {:?}", total).unwrap();
+    table
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::input::InputError;
+
+    #[test]
+    fn test_summary_table() {
+        let reports = vec![
+            PartReport {
+                day: 9,
+                part: 1,
+                variant: None,
+                status: PartStatus::Answer(13.into()),
+                elapsed: Duration::from_millis(2),
+                parse_time: Some(Duration::from_micros(500)),
+                allocs: None,
+                input: PathBuf::from("in/09_p1.txt"),
+            },
+            PartReport {
+                day: 10,
+                part: 3,
+                variant: None,
+                status: PartStatus::NoInput(InputError::Missing(PathBuf::from("in/10_p3.txt"))),
+                elapsed: Duration::ZERO,
+                parse_time: None,
+                allocs: None,
+                input: PathBuf::from("in/10_p3.txt"),
+            },
+        ];
+        let table = summary_table(&reports, Duration::from_millis(3));
+        assert_eq!(
+            table,
+            "Day  Part  Status    Answer        Parse  Elapsed
+---  ----  --------  ------------  -----  -------
+  9     1  answer    13            500µs  2ms
+ 10     3  no input  in/10_p3.txt
+Total: 3ms"
+        );
+    }
+}
@@ -0,0 +1,185 @@
+use std::{
+    fmt::Display,
+    fs::OpenOptions,
+    io,
+    path::{Path, PathBuf},
+};
+
+use crate::input;
+
+const TEMPLATE: &str = include_str!("dayX.rs");
+
+/// Answer types that can be converted into an [`crate::solution::Answer`]
+pub const ANSWER_TYPES: [&str; 5] = ["i32", "i64", "usize", "u128", "String"];
+
+#[derive(Debug)]
+pub enum ScaffoldError {
+    AlreadyExists(PathBuf),
+    /// the file does not look like we expect, so we don't know where to register the day
+    NoInsertionPoint(PathBuf),
+    Io(PathBuf, io::Error),
+}
+
+impl Display for ScaffoldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScaffoldError::AlreadyExists(path) => {
+                write!(f, "{} already exists, not overwriting it", path.display())
+            }
+            ScaffoldError::NoInsertionPoint(path) => {
+                write!(
+                    f,
+                    "don't know where to register the day in {}",
+                    path.display()
+                )
+            }
+            ScaffoldError::Io(path, e) => write!(f, "{}: {}", path.display(), e),
+        }
+    }
+}
+
+impl std::error::Error for ScaffoldError {}
+
+fn render_template(day: usize, answer_type: &str) -> String {
+    TEMPLATE
+        .lines()
+        .skip(1) // "Template for new days"
+        .collect::<Vec<_>>()
+        .join("\n")
+        .replace("DayX", &format!("Day{}", day))
+        .replace("DAY: usize = X;", &format!("DAY: usize = {};", day))
+        .replace("AnswerT", answer_type)
+        + "\n"
+}
+
+/// Inserts `new_line` among the lines that belong to a day (according to `day_of_line`), keeping them sorted by day.
+/// Returns None if there are no such lines or the day is already there
+fn insert_sorted(
+    src: &str,
+    day: usize,
+    new_line: &str,
+    day_of_line: impl Fn(&str) -> Option<usize>,
+) -> Option<String> {
+    let lines = src.lines().collect::<Vec<_>>();
+    let days = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, line)| Some((i, day_of_line(line)?)))
+        .collect::<Vec<_>>();
+    if days.iter().any(|(_, d)| *d == day) {
+        return None;
+    }
+    let insert_at = match days.iter().rev().find(|(_, d)| *d < day) {
+        Some((i, _)) => i + 1,
+        None => days.first()?.0,
+    };
+    let mut lines = lines;
+    lines.insert(insert_at, new_line);
+    Some(lines.join("\n") + "\n")
+}
+
+fn mod_day(line: &str) -> Option<usize> {
+    line.strip_prefix("mod day")?
+        .strip_suffix(';')?
+        .parse()
+        .ok()
+}
+
+fn registry_day(line: &str) -> Option<usize> {
+    let rest = line
+        .trim()
+        .strip_prefix("&ErasedSolution::new(crate::day")?;
+    rest.split_once("::")?.0.parse().ok()
+}
+
+fn read(path: &Path) -> Result<String, ScaffoldError> {
+    std::fs::read_to_string(path).map_err(|e| ScaffoldError::Io(path.to_path_buf(), e))
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), ScaffoldError> {
+    std::fs::write(path, contents).map_err(|e| ScaffoldError::Io(path.to_path_buf(), e))
+}
+
+/// Creates src/day<day>.rs from the template, registers it in main.rs and the registry
+/// and creates empty input files. Returns all files that were created or changed
+pub fn scaffold(
+    day: usize,
+    answer_type: &str,
+    src_dir: &Path,
+    input_dir: &Path,
+) -> Result<Vec<PathBuf>, ScaffoldError> {
+    let day_path = src_dir.join(format!("day{}.rs", day));
+    if day_path.exists() {
+        return Err(ScaffoldError::AlreadyExists(day_path));
+    }
+    // prepare all changes first, so we don't end up with half a day
+    let main_path = src_dir.join("main.rs");
+    let main = insert_sorted(
+        &read(&main_path)?,
+        day,
+        &format!("mod day{};", day),
+        mod_day,
+    )
+    .ok_or_else(|| ScaffoldError::NoInsertionPoint(main_path.clone()))?;
+    let registry_path = src_dir.join("registry.rs");
+    let registry = insert_sorted(
+        &read(&registry_path)?,
+        day,
+        &format!("    &ErasedSolution::new(crate::day{0}::Day{0}),", day),
+        registry_day,
+    )
+    .ok_or_else(|| ScaffoldError::NoInsertionPoint(registry_path.clone()))?;
+
+    write(&day_path, &render_template(day, answer_type))?;
+    write(&main_path, &main)?;
+    write(&registry_path, &registry)?;
+    let mut changed = vec![day_path, main_path, registry_path];
+
+    std::fs::create_dir_all(input_dir).map_err(|e| ScaffoldError::Io(input_dir.into(), e))?;
+    for part in 1..=3 {
+        let path = input::input_path(input_dir, day, part);
+        // create_new never touches existing inputs
+        match OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(_) => changed.push(path),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => (),
+            Err(e) => return Err(ScaffoldError::Io(path, e)),
+        }
+    }
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_render_template() {
+        let rendered = render_template(12, "String");
+        assert!(rendered.contains("pub struct Day12;"));
+        assert!(rendered.contains("impl Solution<String> for Day12 {"));
+        assert!(rendered.contains("const DAY: usize = 12;"));
+        assert!(!rendered.contains("DayX"));
+        assert!(!rendered.contains("AnswerT"));
+    }
+
+    #[test]
+    fn test_insert_sorted() {
+        let main = "mod cli;\nmod day1;\nmod day2;\nmod day10;\nmod input;\n";
+        assert_eq!(
+            insert_sorted(main, 3, "mod day3;", mod_day).unwrap(),
+            "mod cli;\nmod day1;\nmod day2;\nmod day3;\nmod day10;\nmod input;\n"
+        );
+        assert_eq!(
+            insert_sorted(main, 11, "mod day11;", mod_day).unwrap(),
+            "mod cli;\nmod day1;\nmod day2;\nmod day10;\nmod day11;\nmod input;\n"
+        );
+        assert_eq!(insert_sorted(main, 2, "mod day2;", mod_day), None);
+        assert_eq!(insert_sorted("mod cli;\n", 2, "mod day2;", mod_day), None);
+    }
+
+    #[test]
+    fn test_current_sources_have_insertion_points() {
+        assert!(insert_sorted(include_str!("main.rs"), 99, "", mod_day).is_some());
+        assert!(insert_sorted(include_str!("registry.rs"), 99, "", registry_day).is_some());
+    }
+}
@@ -1,3 +1,9 @@
+use std::{
+    fmt::Display,
+    marker::PhantomData,
+    time::{Duration, Instant},
+};
+
 /// The type implementing this trait should not hold any information, it is just a workaround for dynamically dispatching the correct methods
 /// If you want to keep state, create another struct and instantiate it in the solution
 pub trait Solution<T> {
@@ -5,7 +11,285 @@ pub trait Solution<T> {
     fn get_day(&self) -> usize {
         Self::DAY
     }
-    fn part1(&self, input: &str) -> Option<T>;
-    fn part2(&self, input: &str) -> Option<T>;
-    fn part3(&self, input: &str) -> Option<T>;
+    fn part1(&self, input: &str) -> PartResult<T>;
+    fn part2(&self, input: &str) -> PartResult<T>;
+    fn part3(&self, input: &str) -> PartResult<T>;
+}
+
+/// For days where all parts work on the same parsed representation of the input.
+/// Every [`Solution`] is also a `ParsedSolution`, with the raw input as parsed type
+pub trait ParsedSolution<T> {
+    const DAY: usize;
+    /// false for plain [`Solution`]s, which parse inside every part
+    const HAS_PARSE_STEP: bool = true;
+    /// may borrow from the input
+    type Parsed<'a>;
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Parsed<'a>, ParseError>;
+
+    fn part1(&self, parsed: &Self::Parsed<'_>) -> PartResult<T>;
+    fn part2(&self, parsed: &Self::Parsed<'_>) -> PartResult<T>;
+    fn part3(&self, parsed: &Self::Parsed<'_>) -> PartResult<T>;
+
+    /// Runs the given part (1-3) on already parsed input
+    fn run_parsed(&self, part: usize, parsed: &Self::Parsed<'_>) -> PartResult<T> {
+        match part {
+            1 => self.part1(parsed),
+            2 => self.part2(parsed),
+            3 => self.part3(parsed),
+            _ => panic!("Invalid part"),
+        }
+    }
+
+    /// Parses and runs a part in one go
+    fn solve(&self, part: usize, input: &str) -> PartResult<T> {
+        self.run_parsed(part, &self.parse(input)?)
+    }
+}
+
+impl<T, S: Solution<T>> ParsedSolution<T> for S {
+    const DAY: usize = <S as Solution<T>>::DAY;
+    const HAS_PARSE_STEP: bool = false;
+    type Parsed<'a> = &'a str;
+
+    fn parse<'a>(&self, input: &'a str) -> Result<&'a str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(&self, input: &&str) -> PartResult<T> {
+        Solution::part1(self, input)
+    }
+
+    fn part2(&self, input: &&str) -> PartResult<T> {
+        Solution::part2(self, input)
+    }
+
+    fn part3(&self, input: &&str) -> PartResult<T> {
+        Solution::part3(self, input)
+    }
+}
+
+/// Ok(None) means the input was fine, but there is no solution
+pub type PartResult<T> = Result<Option<T>, ParseError>;
+
+/// Something is wrong with the puzzle input. Line and column are 1-based
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A [`ParseError`] together with where it happened, created by [`DynSolution::run_part`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolutionError {
+    pub day: usize,
+    pub part: usize,
+    pub error: ParseError,
+    /// the offending line of the input, if it exists
+    pub source_line: Option<String>,
+}
+
+impl SolutionError {
+    /// Multi-line description pointing at the offending input, similar to compiler errors
+    pub fn diagnostic(&self) -> String {
+        let mut diagnostic = format!(
+            "error: {}\n --> day {} part {}, line {}, column {}",
+            self.error.message, self.day, self.part, self.error.line, self.error.column
+        );
+        if let Some(source_line) = &self.source_line {
+            let gutter = " ".repeat(self.error.line.to_string().len());
+            diagnostic += &format!(
+                "\n{} |\n{} | {}\n{} | {}^",
+                gutter,
+                self.error.line,
+                source_line,
+                gutter,
+                " ".repeat(self.error.column.saturating_sub(1))
+            );
+        }
+        diagnostic
+    }
+}
+
+impl Display for SolutionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "day {} part {}, {}", self.day, self.part, self.error)
+    }
+}
+
+impl std::error::Error for SolutionError {}
+
+/// Common representation of all answer types. The Display impl is the canonical format, used for printing and comparing answers
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Int(i64),
+    BigInt(u128),
+    Text(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{}", n),
+            Answer::BigInt(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(n: i32) -> Self {
+        Answer::Int(n.into())
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(n: i64) -> Self {
+        Answer::Int(n)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Answer::BigInt(n as u128)
+    }
+}
+
+impl From<u128> for Answer {
+    fn from(n: u128) -> Self {
+        Answer::BigInt(n)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_string())
+    }
+}
+
+/// Object safe counterpart of [`Solution`]. Every day has its own answer type, so the answers are converted into an [`Answer`] here.
+/// This allows keeping all days in one list, see [`crate::registry`]
+pub trait DynSolution: Send + Sync {
+    fn day(&self) -> usize;
+    /// Runs the given part (1-3), also measuring how long parsing took
+    fn run_part_timed(&self, part: usize, input: &str) -> TimedRun;
+
+    /// Runs the given part (1-3)
+    fn run_part(&self, part: usize, input: &str) -> Result<Option<Answer>, SolutionError> {
+        self.run_part_timed(part, input).result
+    }
+}
+
+/// Result of [`DynSolution::run_part_timed`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimedRun {
+    pub result: Result<Option<Answer>, SolutionError>,
+    /// None if the solution has no separate parse step
+    pub parse_time: Option<Duration>,
+}
+
+/// Adapter turning any [`Solution`] or [`ParsedSolution`] into a [`DynSolution`]
+pub struct ErasedSolution<S, T> {
+    solution: S,
+    // fn() -> T so the adapter is Send + Sync regardless of T
+    answer: PhantomData<fn() -> T>,
+}
+
+impl<S, T> ErasedSolution<S, T> {
+    pub const fn new(solution: S) -> Self {
+        Self {
+            solution,
+            answer: PhantomData,
+        }
+    }
+}
+
+impl<S, T> DynSolution for ErasedSolution<S, T>
+where
+    S: ParsedSolution<T> + Send + Sync,
+    T: Into<Answer>,
+{
+    fn day(&self) -> usize {
+        S::DAY
+    }
+
+    fn run_part_timed(&self, part: usize, input: &str) -> TimedRun {
+        let now = Instant::now();
+        let parsed = self.solution.parse(input);
+        let parse_time = S::HAS_PARSE_STEP.then(|| now.elapsed());
+        let answer = parsed.and_then(|parsed| self.solution.run_parsed(part, &parsed));
+        let result = match answer {
+            Ok(answer) => Ok(answer.map(Into::into)),
+            Err(error) => Err(SolutionError {
+                day: self.day(),
+                part,
+                source_line: input
+                    .lines()
+                    .nth(error.line.wrapping_sub(1))
+                    .map(str::to_string),
+                error,
+            }),
+        };
+        TimedRun { result, parse_time }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_answer_display() {
+        assert_eq!(Answer::from(-3).to_string(), "-3");
+        assert_eq!(Answer::from(u128::MAX).to_string(), u128::MAX.to_string());
+        // strings are not quoted, unlike their Debug output
+        assert_eq!(Answer::from("RRB@").to_string(), "RRB@");
+    }
+
+    #[test]
+    fn test_diagnostic() {
+        let error = SolutionError {
+            day: 7,
+            part: 1,
+            error: ParseError::new(2, 5, "invalid instruction `x`"),
+            source_line: Some("B:+,x,-".to_string()),
+        };
+        assert_eq!(
+            error.diagnostic(),
+            "error: invalid instruction `x`
+ --> day 7 part 1, line 2, column 5
+  |
+2 | B:+,x,-
+  |     ^"
+        );
+    }
 }
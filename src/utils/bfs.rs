@@ -1,12 +1,19 @@
 use std::{
+    cmp::Ordering,
     collections::{BinaryHeap, HashMap, HashSet, VecDeque},
     hash::Hash,
+    iter::Rev,
+    ops::Add,
+    vec,
 };
 
+/// nodes from the start to the goal, both included
+pub type Path<N> = Rev<vec::IntoIter<N>>;
+
 fn build_path_from_precursors<G: Graph>(
     goal_node: &G::Node,
     precursors: &HashMap<G::Node, G::Node>,
-) -> impl Iterator<Item = G::Node> {
+) -> Path<G::Node> {
     let mut path = vec![goal_node.clone()];
     let mut current = goal_node.clone();
     while let Some(node) = precursors.get(&current) {
@@ -22,12 +29,37 @@ pub fn bfs<G: Graph>(
     start_node: G::Node,
     goal_node: G::Node,
 ) -> Option<impl Iterator<Item = G::Node>> {
-    let mut queue = VecDeque::from([start_node.clone()]);
-    let mut visited = HashSet::from([start_node.clone()]);
+    bfs_until(graph, start_node, |node| *node == goal_node).map(|(_, path)| path)
+}
+
+/// returns the closest node for which is_goal is true, and the path to it
+pub fn bfs_until<G: Graph>(
+    graph: &mut G,
+    start_node: G::Node,
+    is_goal: impl FnMut(&G::Node) -> bool,
+) -> Option<(G::Node, Path<G::Node>)> {
+    bfs_multi(graph, [start_node], is_goal)
+}
+
+/// bfs from all start nodes at the same time, as if they were one.
+/// returns the closest node for which is_goal is true, and the path to it from the nearest start node
+pub fn bfs_multi<G: Graph>(
+    graph: &mut G,
+    start_nodes: impl IntoIterator<Item = G::Node>,
+    mut is_goal: impl FnMut(&G::Node) -> bool,
+) -> Option<(G::Node, Path<G::Node>)> {
+    let mut queue = VecDeque::new();
+    let mut visited = HashSet::new();
+    for start_node in start_nodes {
+        if visited.insert(start_node.clone()) {
+            queue.push_back(start_node);
+        }
+    }
     let mut precursors = HashMap::new();
     while let Some(node) = queue.pop_front() {
-        if node == goal_node {
-            return Some(build_path_from_precursors::<G>(&goal_node, &precursors));
+        if is_goal(&node) {
+            let path = build_path_from_precursors::<G>(&node, &precursors);
+            return Some((node, path));
         }
         for neighbor in graph.neighbors(&node) {
             if !visited.contains(&neighbor) {
@@ -37,21 +69,226 @@ pub fn bfs<G: Graph>(
             }
         }
     }
-    return None;
+    None
+}
+
+/// How [`bfs_iter`] reached a node
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reached<N> {
+    pub depth: usize,
+    /// None for the start node
+    pub precursor: Option<N>,
 }
-/*
+
+/// Yields every node reachable from the start together with its depth, in bfs order.
+/// Nodes are only expanded when they are yielded, so stopping early saves the rest of the search
+pub struct BfsIter<'g, G: Graph> {
+    graph: &'g mut G,
+    queue: VecDeque<G::Node>,
+    reached: HashMap<G::Node, Reached<G::Node>>,
+    max_depth: Option<usize>,
+}
+
+impl<G: Graph> Iterator for BfsIter<'_, G> {
+    type Item = (G::Node, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.queue.pop_front()?;
+        let depth = self.reached[&node].depth;
+        if self.max_depth.is_none_or(|max_depth| depth < max_depth) {
+            for neighbor in self.graph.neighbors(&node) {
+                if !self.reached.contains_key(&neighbor) {
+                    let reached = Reached {
+                        depth: depth + 1,
+                        precursor: Some(node.clone()),
+                    };
+                    self.reached.insert(neighbor.clone(), reached);
+                    self.queue.push_back(neighbor);
+                }
+            }
+        }
+        Some((node, depth))
+    }
+}
+
+/// lazy bfs from start, nodes further away than max_depth are left out
+pub fn bfs_iter<G: Graph>(
+    graph: &mut G,
+    start_node: G::Node,
+    max_depth: Option<usize>,
+) -> BfsIter<'_, G> {
+    let start = Reached {
+        depth: 0,
+        precursor: None,
+    };
+    BfsIter {
+        graph,
+        queue: VecDeque::from([start_node.clone()]),
+        reached: HashMap::from([(start_node, start)]),
+        max_depth,
+    }
+}
+
+/// Every node reachable from the start, with its distance and the node it was reached from
+#[derive(Debug, Clone)]
+pub struct BfsMap<N> {
+    reached: HashMap<N, Reached<N>>,
+}
+
+impl<N: Clone + Eq + Hash> BfsMap<N> {
+    pub fn get(&self, node: &N) -> Option<&Reached<N>> {
+        self.reached.get(node)
+    }
+
+    pub fn distance(&self, node: &N) -> Option<usize> {
+        self.get(node).map(|reached| reached.depth)
+    }
+
+    /// shortest path from the start to node, None if node was not reached
+    // only the distances are needed so far
+    #[allow(dead_code)]
+    pub fn path_to(&self, node: &N) -> Option<Path<N>> {
+        let mut path = vec![node.clone()];
+        let mut current = self.get(node)?;
+        while let Some(precursor) = &current.precursor {
+            path.push(precursor.clone());
+            current = &self.reached[precursor];
+        }
+        Some(path.into_iter().rev())
+    }
+}
+
+/// runs [`bfs_iter`] to the end and keeps everything it reached
+pub fn bfs_map<G: Graph>(
+    graph: &mut G,
+    start_node: G::Node,
+    max_depth: Option<usize>,
+) -> BfsMap<G::Node> {
+    let mut iter = bfs_iter(graph, start_node, max_depth);
+    iter.by_ref().for_each(drop);
+    BfsMap {
+        reached: iter.reached,
+    }
+}
+
+/// Entry of the priority queues. Ordered so that std's BinaryHeap, a max heap, pops the lowest priority first.
+/// Ties go to the larger distance, the node itself is never compared
+struct QueueEntry<N, D> {
+    priority: D,
+    distance: D,
+    node: N,
+}
+
+impl<N, D: Distance> QueueEntry<N, D> {
+    fn new(priority: D, distance: D, node: N) -> Self {
+        Self {
+            priority,
+            distance,
+            node,
+        }
+    }
+}
+
+impl<N, D: Distance> Ord for QueueEntry<N, D> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .priority
+            .cmp(&self.priority)
+            .then(self.distance.cmp(&other.distance))
+    }
+}
+
+impl<N, D: Distance> PartialOrd for QueueEntry<N, D> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, D: Distance> PartialEq for QueueEntry<N, D> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<N, D: Distance> Eq for QueueEntry<N, D> {}
+
+/// Estimates the remaining distance from node to goal for [`astar`].
+/// It must never overestimate, otherwise the path found may not be the shortest
+pub trait Heuristic<N, D> {
+    fn estimate(&self, node: &N, goal: &N) -> D;
+}
+
+impl<N, D, F: Fn(&N, &N) -> D> Heuristic<N, D> for F {
+    fn estimate(&self, node: &N, goal: &N) -> D {
+        self(node, goal)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchResult<N, D> {
+    pub distance: D,
+    pub path: Vec<N>,
+    /// how many nodes had their neighbors looked at, lower is better
+    pub expanded: usize,
+}
+
+/// shortest path to the goal, which is just a* without a heuristic
+// no weighted puzzle so far, but the next one is bound to come
+#[allow(dead_code)]
 pub fn dijkstra<G: DirectedGraph>(
     graph: &mut G,
     start_node: G::Node,
     goal_node: G::Node,
-) -> Option<impl Iterator<Item = G::Node>> {
-    let mut queue = BinaryHeap::from([start_node]);
-    let mut distances = HashMap::from([(start_node.clone(), 0)]);
-    //let mut precursors = HashMap::new();
-    while let Some(node) = queue.pop() {}
-    todo!()
-    //build_path_from_precursors(&goal_node, &precursors)
-} */
+) -> Option<SearchResult<G::Node, G::Distance>> {
+    astar(graph, start_node, goal_node, &|_: &G::Node, _: &G::Node| {
+        G::Distance::ZERO
+    })
+}
+
+/// dijkstra, but nodes that look closer to the goal according to the heuristic are expanded first
+#[allow(dead_code)]
+pub fn astar<G: DirectedGraph>(
+    graph: &mut G,
+    start_node: G::Node,
+    goal_node: G::Node,
+    heuristic: &impl Heuristic<G::Node, G::Distance>,
+) -> Option<SearchResult<G::Node, G::Distance>> {
+    let zero = G::Distance::ZERO;
+    let start_estimate = heuristic.estimate(&start_node, &goal_node);
+    // ordered by the estimated total distance. On ties the node furthest along goes first,
+    // as it is likely closer to the goal
+    let mut queue = BinaryHeap::from([QueueEntry::new(start_estimate, zero, start_node.clone())]);
+    let mut distances = HashMap::from([(start_node, zero)]);
+    let mut precursors = HashMap::new();
+    let mut expanded = 0;
+    while let Some(QueueEntry { distance, node, .. }) = queue.pop() {
+        if node == goal_node {
+            return Some(SearchResult {
+                distance,
+                path: build_path_from_precursors::<G>(&goal_node, &precursors).collect(),
+                expanded,
+            });
+        }
+        // stale entry, the node was reached on a shorter path in the meantime
+        if distances.get(&node).is_some_and(|&known| known < distance) {
+            continue;
+        }
+        expanded += 1;
+        for (neighbor, edge) in graph.neighbors_with_distance(&node) {
+            let new_distance = distance + edge;
+            if distances
+                .get(&neighbor)
+                .is_none_or(|&known| new_distance < known)
+            {
+                let estimate = new_distance + heuristic.estimate(&neighbor, &goal_node);
+                distances.insert(neighbor.clone(), new_distance);
+                precursors.insert(neighbor.clone(), node.clone());
+                queue.push(QueueEntry::new(estimate, new_distance, neighbor));
+            }
+        }
+    }
+    None
+}
 
 pub trait Graph {
     /// Node should be easy to clone etc, we do this a lot
@@ -60,11 +297,25 @@ pub trait Graph {
     fn neighbors(&mut self, n: &Self::Node) -> impl Iterator<Item = Self::Node>;
 }
 
+/// Edge weights and path lengths, edges must not be negative
+pub trait Distance: Copy + Ord + Add<Output = Self> {
+    const ZERO: Self;
+}
+
+macro_rules! impl_distance {
+    ($($t:ty),*) => {
+        $(impl Distance for $t {
+            const ZERO: Self = 0;
+        })*
+    };
+}
+
+impl_distance!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
 pub trait DirectedGraph {
     /// Node should be easy to clone etc, we do this a lot
-    /// it also needs to be Ord for a directed graph and Ord must return in reverse order (because std's BinaryHeap is a MaxHeap)
-    type Node: Clone + Eq + Hash + Ord;
-    type Distance: Copy + Clone + Eq + Hash;
+    type Node: Clone + Eq + Hash;
+    type Distance: Distance;
 
     fn neighbors_with_distance(
         &mut self,
@@ -76,6 +327,205 @@ impl<G: DirectedGraph> Graph for G {
     type Node = G::Node;
 
     fn neighbors(&mut self, n: &Self::Node) -> impl Iterator<Item = Self::Node> {
-        self.neighbors_with_distance(n).map(|(n, d)| n)
+        self.neighbors_with_distance(n).map(|(n, _)| n)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// adjacency list with weighted edges
+    struct WeightedGraph(HashMap<char, Vec<(char, u32)>>);
+
+    impl WeightedGraph {
+        fn new(edges: &[(char, char, u32)]) -> Self {
+            let mut adjacency = HashMap::<_, Vec<_>>::new();
+            for &(from, to, distance) in edges {
+                adjacency.entry(from).or_default().push((to, distance));
+            }
+            Self(adjacency)
+        }
+    }
+
+    impl DirectedGraph for WeightedGraph {
+        type Node = char;
+        type Distance = u32;
+
+        fn neighbors_with_distance(&mut self, n: &char) -> impl Iterator<Item = (char, u32)> {
+            self.0.get(n).into_iter().flatten().copied()
+        }
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        // the direct edge is more expensive than the detour
+        let mut graph = WeightedGraph::new(&[
+            ('a', 'b', 7),
+            ('a', 'c', 2),
+            ('c', 'd', 2),
+            ('d', 'b', 1),
+            ('b', 'e', 1),
+            ('c', 'e', 10),
+        ]);
+        let result = dijkstra(&mut graph, 'a', 'e').unwrap();
+        assert_eq!(result.distance, 6);
+        assert_eq!(result.path.into_iter().collect::<String>(), "acdbe");
+
+        let result = dijkstra(&mut graph, 'c', 'c').unwrap();
+        assert_eq!(result.distance, 0);
+        assert_eq!(result.path, ['c']);
+
+        // edges are directed
+        assert!(dijkstra(&mut graph, 'e', 'a').is_none());
+        // bfs ignores the weights and takes the fewest edges
+        assert_eq!(
+            bfs(&mut graph, 'a', 'e').unwrap().collect::<String>(),
+            "abe"
+        );
+    }
+
+    #[test]
+    fn test_bfs_goal_predicate() {
+        let mut maze = small_maze();
+        // closest cell of the right column
+        let (goal, path) = bfs_until(&mut maze, (0, 0), |&(x, _)| x == 3).unwrap();
+        assert_eq!(goal, (3, 2));
+        assert_eq!(path.count(), 6);
+        assert!(bfs_until(&mut maze, (0, 0), |&(x, _)| x == 4).is_none());
+
+        // starting from the whole bottom row, the top row is two steps away from the left side
+        let bottom_row = (0..4).map(|x| (x, 2));
+        let (goal, path) = bfs_multi(&mut maze, bottom_row, |&(_, y)| y == 0).unwrap();
+        assert_eq!(goal, (0, 0));
+        assert_eq!(path.collect::<Vec<_>>(), [(0, 2), (0, 1), (0, 0)]);
+
+        // a start that is already a goal wins
+        let (goal, path) = bfs_multi(&mut maze, [(1, 1), (3, 0)], |&(_, y)| y == 0).unwrap();
+        assert_eq!(goal, (3, 0));
+        assert_eq!(path.count(), 1);
+    }
+
+    #[test]
+    fn test_bfs_map() {
+        let mut maze = small_maze();
+        let map = bfs_map(&mut maze, (0, 0), None);
+        assert_eq!(map.distance(&(0, 0)), Some(0));
+        assert_eq!(map.distance(&(3, 0)), Some(7));
+        assert_eq!(map.distance(&(2, 0)), None);
+        assert_eq!(map.get(&(1, 0)).unwrap().precursor, Some((0, 0)));
+        let path = map.path_to(&(3, 1)).unwrap().collect::<Vec<_>>();
+        assert_eq!(path.len(), 7);
+        assert_eq!(path.first(), Some(&(0, 0)));
+        assert!(map.path_to(&(2, 1)).is_none());
+
+        let near = bfs_map(&mut maze, (0, 0), Some(2));
+        assert_eq!(near.distance(&(1, 1)), Some(2));
+        assert_eq!(near.distance(&(2, 2)), None);
+    }
+
+    #[test]
+    fn test_bfs_iter() {
+        let mut maze = small_maze();
+        let depths = bfs_iter(&mut maze, (0, 0), None)
+            .map(|(_, depth)| depth)
+            .collect::<Vec<_>>();
+        assert_eq!(depths, [0, 1, 1, 2, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(
+            bfs_iter(&mut maze, (3, 0), Some(1)).collect::<Vec<_>>(),
+            [((3, 0), 0), ((3, 1), 1)]
+        );
+        // only the yielded nodes were expanded, the far side of the maze was never reached
+        let mut iter = bfs_iter(&mut maze, (0, 0), None);
+        assert_eq!(iter.nth(2).map(|(_, depth)| depth), Some(1));
+        assert_eq!(iter.reached.len(), 5);
+    }
+
+    /// nodes that can't be ordered, the search only compares distances
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    struct Room(&'static str);
+
+    struct Rooms;
+
+    impl DirectedGraph for Rooms {
+        type Node = Room;
+        type Distance = i64;
+
+        fn neighbors_with_distance(&mut self, n: &Room) -> impl Iterator<Item = (Room, i64)> {
+            let doors: &[(&str, i64)] = match n.0 {
+                "hall" => &[("kitchen", 3), ("stairs", 1)],
+                "stairs" => &[("attic", 5), ("kitchen", 1)],
+                "kitchen" => &[("attic", 2)],
+                _ => &[],
+            };
+            doors.iter().map(|&(room, distance)| (Room(room), distance))
+        }
+    }
+
+    #[test]
+    fn test_unordered_nodes() {
+        let result = dijkstra(&mut Rooms, Room("hall"), Room("attic")).unwrap();
+        assert_eq!(result.distance, 4);
+        assert_eq!(
+            result
+                .path
+                .into_iter()
+                .map(|room| room.0)
+                .collect::<Vec<_>>(),
+            ["hall", "stairs", "kitchen", "attic"]
+        );
+    }
+
+    /// open grid, # are walls. Moving costs 1 in the four directions
+    struct Maze(Vec<Vec<char>>);
+
+    impl DirectedGraph for Maze {
+        type Node = (usize, usize);
+        type Distance = usize;
+
+        fn neighbors_with_distance(
+            &mut self,
+            &(x, y): &(usize, usize),
+        ) -> impl Iterator<Item = ((usize, usize), usize)> {
+            [(0, 1), (1, 0), (0, -1), (-1, 0)]
+                .into_iter()
+                .filter_map(move |(dx, dy)| {
+                    let (x, y) = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+                    (*self.0.get(y)?.get(x)? != '#').then_some(((x, y), 1))
+                })
+        }
+    }
+
+    /// a wall splits the top of the maze, the bottom row connects both sides
+    fn small_maze() -> Maze {
+        Maze(
+            ["..#.", "..#.", "...."]
+                .map(|row| row.chars().collect())
+                .to_vec(),
+        )
+    }
+
+    #[test]
+    fn test_astar() {
+        let mut maze = Maze(
+            ["......", "..##..", "......", "......"]
+                .map(|row| row.chars().collect())
+                .to_vec(),
+        );
+        let manhattan =
+            |a: &(usize, usize), b: &(usize, usize)| a.0.abs_diff(b.0) + a.1.abs_diff(b.1);
+        let result = astar(&mut maze, (0, 0), (5, 3), &manhattan).unwrap();
+        assert_eq!(result.distance, 8);
+        assert_eq!(result.path.len(), 9);
+        assert_eq!(result.path.first(), Some(&(0, 0)));
+        assert_eq!(result.path.last(), Some(&(5, 3)));
+
+        // without a heuristic it is dijkstra and has to look at more nodes
+        let blind = dijkstra(&mut maze, (0, 0), (5, 3)).unwrap();
+        assert_eq!(blind.distance, 8);
+        assert!(result.expanded < blind.expanded);
+
+        // walls can't be reached
+        assert!(astar(&mut maze, (0, 0), (2, 1), &manhattan).is_none());
     }
 }
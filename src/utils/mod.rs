@@ -1,2 +1,3 @@
 pub mod bfs;
 pub mod cycle_finder;
+pub mod parse;
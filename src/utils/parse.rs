@@ -0,0 +1,53 @@
+use std::str::FromStr;
+
+use crate::solution::ParseError;
+
+/// 1-based column of `token` in `line`. The token has to be a slice of the line, e.g. from split()
+pub fn column_of(line: &str, token: &str) -> usize {
+    let line_range = line.as_bytes().as_ptr_range();
+    let token_range = token.as_bytes().as_ptr_range();
+    debug_assert!(
+        line_range.start <= token_range.start && token_range.end <= line_range.end,
+        "`{}` is not a slice of `{}`",
+        token,
+        line
+    );
+    let offset = token_range.start as usize - line_range.start as usize;
+    line[..offset].chars().count() + 1
+}
+
+/// `i64` for i64, `Foo` for some::module::Foo
+fn short_type_name<T>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+/// Parses `token` (a slice of `line`), pointing at the token if it is not a valid T
+pub fn parse_token<T: FromStr>(line_nr: usize, line: &str, token: &str) -> Result<T, ParseError> {
+    token.parse().map_err(|_| {
+        ParseError::new(
+            line_nr,
+            column_of(line, token),
+            format!("expected {}, got `{}`", short_type_name::<T>(), token),
+        )
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_token() {
+        let line = "12 x4 7";
+        let tokens = line.split(' ').collect::<Vec<_>>();
+        assert_eq!(parse_token::<i64>(3, line, tokens[2]), Ok(7));
+        assert_eq!(
+            parse_token::<i64>(3, line, tokens[1]),
+            Err(ParseError::new(3, 4, "expected i64, got `x4`"))
+        );
+        // columns count characters, not bytes
+        let line = "ä 5";
+        assert_eq!(column_of(line, &line[3..]), 3);
+    }
+}
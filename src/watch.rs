@@ -0,0 +1,89 @@
+use std::{
+    io::{self, Write},
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::{
+    runner::{self, RunConfig},
+    solution::DynSolution,
+};
+
+pub const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Moves the cursor to the top left and clears the terminal
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// What we know about a file without reading it. Missing files are None, so creating one counts as a change
+type FileState = Option<(SystemTime, u64)>;
+
+/// Input files the selected parts would read, including the variants that exist right now
+pub fn watched_files(
+    solutions: &[&'static dyn DynSolution],
+    parts: &[usize],
+    config: &RunConfig,
+) -> Vec<PathBuf> {
+    let mut paths = runner::plan(solutions, parts, config)
+        .iter()
+        .map(|run| run.input_path(config))
+        .collect::<Vec<_>>();
+    paths.dedup();
+    paths
+}
+
+fn file_state(path: &Path) -> FileState {
+    let metadata = std::fs::metadata(path).ok()?;
+    // the length catches edits within the timestamp resolution of the file system
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+fn snapshot(paths: &[PathBuf]) -> Vec<FileState> {
+    paths.iter().map(|path| file_state(path)).collect()
+}
+
+/// Calls run once, and again after clearing the screen whenever one of the files changes. Never returns
+pub fn watch(paths: &[PathBuf], mut run: impl FnMut()) -> ! {
+    let mut last = snapshot(paths);
+    loop {
+        run();
+        println!();
+        println!(
+            "Watching {} input files for changes, Ctrl-C to stop",
+            paths.len()
+        );
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            let current = snapshot(paths);
+            if current != last {
+                last = current;
+                break;
+            }
+        }
+        print!("{}", CLEAR_SCREEN);
+        let _ = io::stdout().flush();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::input::TempDir;
+
+    #[test]
+    fn test_snapshot() {
+        let dir = TempDir::new("watch");
+        let path = dir.join("01_p1.txt");
+        let paths = [path.clone()];
+        assert_eq!(snapshot(&paths), vec![None]);
+
+        std::fs::write(&path, "1").unwrap();
+        let created = snapshot(&paths);
+        assert!(created[0].is_some());
+        std::fs::write(&path, "12").unwrap();
+        assert_ne!(snapshot(&paths), created);
+
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(snapshot(&paths), vec![None]);
+    }
+}